@@ -0,0 +1,340 @@
+use std::{
+    error::Error as StdError,
+    fmt::{self, Display},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hmac::{Hmac, Mac};
+use http::Method;
+use sha1::Sha1;
+use url::Url;
+
+#[cfg(feature = "rsa-sha1")]
+use crate::RsaSha1;
+use crate::{
+    signer::SignedParameters, util::percent_encode, Clock, OAuthParameters, SecretsProvider, Sign,
+    SignResult, SignatureMethod, Signer, SignerError,
+};
+
+/// Error type returned from the `SigningBackend`.
+pub type BackendError = Box<dyn StdError + Send + Sync>;
+
+/**
+Interface of the signing backend which holds the key outside of this process,
+e.g. KMS, HSM, or a signing daemon.
+
+The backend receives the signature base string and returns the raw signature bytes.
+The signature is encoded with base64 and put in the request as the `oauth_signature`.
+
+# Basic usage
+
+```rust
+use reqwest_oauth1::{BackendError, OAuthBackendProvider, SigningBackend};
+
+# async fn run() -> reqwest_oauth1::Result<()> {
+struct KmsBackend;
+
+#[async_trait::async_trait]
+impl SigningBackend for KmsBackend {
+    fn signature_method_name(&self) -> &'static str {
+        "RSA-SHA1"
+    }
+
+    async fn sign(&self, base_string: &str) -> Result<Vec<u8>, BackendError> {
+        // call your KMS here...
+        # let _ = base_string;
+        Ok(vec![])
+    }
+}
+
+let secrets = reqwest_oauth1::Secrets::new("[CONSUMER_KEY]", "");
+let params = reqwest_oauth1::OAuthParameters::new();
+
+let resp = reqwest::Client::new()
+    .oauth1_with_backend(secrets, params, KmsBackend)
+    .get("https://example.com/")
+    .send()
+    .await?;
+# Ok(())
+# }
+```
+
+*/
+#[async_trait]
+pub trait SigningBackend: Send + Sync {
+    /// The `oauth_signature_method` value of the signature this backend generates.
+    fn signature_method_name(&self) -> &'static str;
+
+    /// Sign the signature base string and return the raw signature bytes.
+    async fn sign(&self, base_string: &str) -> Result<Vec<u8>, BackendError>;
+}
+
+#[async_trait]
+impl<T> SigningBackend for Arc<T>
+where
+    T: SigningBackend + ?Sized,
+{
+    fn signature_method_name(&self) -> &'static str {
+        (**self).signature_method_name()
+    }
+
+    async fn sign(&self, base_string: &str) -> Result<Vec<u8>, BackendError> {
+        (**self).sign(base_string).await
+    }
+}
+
+/**
+The `SigningBackend` which signs in this process.
+
+This is intended to be used as a test double of your remote backends.
+
+# Basic usage
+
+```rust
+use reqwest_oauth1::LocalSigningBackend;
+
+let consumer_secret = "[CONSUMER_SECRET]";
+let token_secret = "[TOKEN_SECRET]";
+
+let backend = LocalSigningBackend::hmac_sha1(consumer_secret, Some(token_secret));
+```
+
+*/
+#[derive(Clone)]
+pub struct LocalSigningBackend {
+    key: LocalKey,
+}
+
+#[derive(Clone)]
+enum LocalKey {
+    HmacSha1(Vec<u8>),
+    #[cfg(feature = "rsa-sha1")]
+    RsaSha1(RsaSha1),
+}
+
+impl LocalSigningBackend {
+    /// Sign with `HMAC-SHA1` with the secrets.
+    pub fn hmac_sha1(consumer_secret: &str, token_secret: Option<&str>) -> Self {
+        let key = format!(
+            "{}&{}",
+            percent_encode(consumer_secret),
+            percent_encode(token_secret.unwrap_or_default())
+        );
+        LocalSigningBackend {
+            key: LocalKey::HmacSha1(key.into_bytes()),
+        }
+    }
+
+    /// Sign with `RSA-SHA1` with the private key.
+    #[cfg(feature = "rsa-sha1")]
+    pub fn rsa_sha1(method: RsaSha1) -> Self {
+        LocalSigningBackend {
+            key: LocalKey::RsaSha1(method),
+        }
+    }
+}
+
+impl fmt::Debug for LocalSigningBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // never print the key
+        f.debug_struct("LocalSigningBackend")
+            .field("signature_method", &self.signature_method_name())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl SigningBackend for LocalSigningBackend {
+    fn signature_method_name(&self) -> &'static str {
+        match self.key {
+            LocalKey::HmacSha1(_) => "HMAC-SHA1",
+            #[cfg(feature = "rsa-sha1")]
+            LocalKey::RsaSha1(_) => "RSA-SHA1",
+        }
+    }
+
+    async fn sign(&self, base_string: &str) -> Result<Vec<u8>, BackendError> {
+        match self.key {
+            LocalKey::HmacSha1(ref key) => {
+                let mut mac = Hmac::<Sha1>::new_from_slice(key)?;
+                mac.update(base_string.as_bytes());
+                Ok(mac.finalize().into_bytes().to_vec())
+            }
+            #[cfg(feature = "rsa-sha1")]
+            LocalKey::RsaSha1(ref method) => Ok(method.sign_base_string(base_string)?),
+        }
+    }
+}
+
+/**
+Provides OAuth signature with the `SigningBackend`.
+
+Unlike `Signer`, the signature is generated asynchronously.
+
+# Note
+
+This struct is intended for internal use.
+
+*/
+#[derive(Debug)]
+pub struct ExternalSigner<'a, TSecrets, TBackend>
+where
+    TSecrets: SecretsProvider + Clone,
+{
+    signer: Signer<'a, TSecrets, Detached>,
+    backend: Arc<TBackend>,
+}
+
+// the backend is shared, so it is not required to be `Clone`
+impl<TSecrets, TBackend> Clone for ExternalSigner<'_, TSecrets, TBackend>
+where
+    TSecrets: SecretsProvider + Clone,
+{
+    fn clone(&self) -> Self {
+        ExternalSigner {
+            signer: self.signer.clone(),
+            backend: self.backend.clone(),
+        }
+    }
+}
+
+impl<'a, TSecrets, TBackend> ExternalSigner<'a, TSecrets, TBackend>
+where
+    TSecrets: SecretsProvider + Clone,
+    TBackend: SigningBackend,
+{
+    /// Construct the signer.
+    ///
+    /// The signature method specified in the `parameters` is ignored,
+    /// the backend signs instead.
+    pub fn new<TSM>(
+        secrets: TSecrets,
+        parameters: OAuthParameters<'a, TSM>,
+        backend: TBackend,
+    ) -> Self
+    where
+        TSM: SignatureMethod + Clone,
+    {
+        let detached = Detached(backend.signature_method_name());
+        ExternalSigner {
+            signer: Signer::new(secrets, parameters.signature_method(detached)),
+            backend: Arc::new(backend),
+        }
+    }
+
+    pub(crate) fn override_oauth_parameter(
+        self,
+        parameters: std::collections::HashMap<String, String>,
+    ) -> Self {
+        ExternalSigner {
+            signer: self.signer.override_oauth_parameter(parameters),
+            ..self
+        }
+    }
+
+    /// Whether the signer refuses to sign the request partially.
+    pub(crate) fn is_strict(&self) -> bool {
+        self.signer.is_strict()
+    }
+
+    /// The `oauth_signature_method` value of the backend.
+    pub(crate) fn signature_method_name(&self) -> &'static str {
+        self.backend.signature_method_name()
+    }
+
+    /// Whether the oauth_timestamp or the oauth_nonce is fixed.
+    pub(crate) fn is_pinned(&self) -> bool {
+        self.signer.is_pinned()
+    }
+
+    /// Replace the clock to generate the oauth_timestamp value.
+    pub(crate) fn clock<T>(self, clock: T) -> Self
+    where
+        T: Clock + 'static,
+    {
+        ExternalSigner {
+            signer: self.signer.clock(clock),
+            ..self
+        }
+    }
+
+    /// Generate OAuth signature with the backend.
+    pub(crate) async fn sign_parameters(
+        self,
+        method: Method,
+        url: Url,
+        payload: &str,
+        body: Option<&[u8]>,
+    ) -> SignResult<SignedParameters> {
+        let mut signed =
+            self.signer
+                .sign_parameters(method.as_str(), url.as_str(), payload, body)?;
+        let signature = self
+            .backend
+            .sign(signed.base_string())
+            .await
+            .map_err(|e| SignerError::Backend(e.to_string()))?;
+        signed.set_signature(percent_encode(&STANDARD.encode(signature)).to_string());
+        Ok(signed)
+    }
+}
+
+/// The signature method which only provides the `oauth_signature_method`.
+///
+/// The signature is generated by the `SigningBackend` later.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Detached(&'static str);
+
+/// A `Sign` which generates no signature.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DetachedSign(&'static str);
+
+impl SignatureMethod for Detached {
+    type Sign = DetachedSign;
+
+    fn sign_with(
+        self,
+        _consumer_secret: impl Display,
+        _token_secret: Option<impl Display>,
+    ) -> DetachedSign {
+        DetachedSign(self.0)
+    }
+}
+
+impl Sign for DetachedSign {
+    type Signature = &'static str;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        self.0
+    }
+
+    fn request_method(&mut self, _method: &str) {}
+
+    fn uri(&mut self, _uri: impl Display) {}
+
+    fn parameter(&mut self, _key: &str, _value: impl Display) {}
+
+    fn delimiter(&mut self) {}
+
+    fn finish(self) -> &'static str {
+        ""
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn local_hmac_sha1() {
+        // https://tools.ietf.org/html/rfc5849#section-3.4.1.1
+        let base_string = "GET&http%3A%2F%2Fphotos.example.net%2Fphotos&file%3Dvacation.jpg%26oauth_consumer_key%3Ddpf43f3p2l4k3l03%26oauth_nonce%3DchapoH%26oauth_signature_method%3DHMAC-SHA1%26oauth_timestamp%3D137131202%26oauth_token%3Dnnch734d00sl2jdk%26size%3Doriginal";
+        let backend = LocalSigningBackend::hmac_sha1("kd94hf93k423kf44", Some("pfkkdhi9sl3r4s00"));
+        assert_eq!(backend.signature_method_name(), "HMAC-SHA1");
+
+        let signature = backend.sign(base_string).await.unwrap();
+        assert_eq!(STANDARD.encode(signature), "MdpQcU8iPSUjWoN/UDMsK2sui9I=");
+    }
+}
@@ -0,0 +1,14 @@
+// ----------------------------------------------------------------------------
+// This source code contains derived artifacts from seanmonstar's `reqwest`.
+// for further information(including license information),
+// please visit their repository: https://github.com/seanmonstar/reqwest .
+// ----------------------------------------------------------------------------
+
+use crate::{blocking::RequestBuilder, client::impl_client};
+
+impl_client!(
+    /// Compatible interface with reqwest's blocking [`Client`](https://docs.rs/reqwest/0.12/reqwest/blocking/struct.Client.html).
+    reqwest::blocking::Client,
+    "reqwest::blocking::Client",
+    RequestBuilder
+);
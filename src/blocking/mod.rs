@@ -0,0 +1,37 @@
+/*!
+The blocking API, which wraps `reqwest::blocking`.
+
+Enabled with the `blocking` feature. It coexists with the asynchronous API
+in the crate root, as same as the layout of reqwest.
+
+The `SigningBackend` is not available in the blocking API, since it signs asynchronously.
+
+```rust
+use reqwest_oauth1::{blocking::TokenReaderBlocking, OAuthClientProvider};
+
+# fn run() -> reqwest_oauth1::Result<()> {
+let secrets = reqwest_oauth1::Secrets::new("[CONSUMER_KEY]", "[CONSUMER_SECRET]");
+let resp = reqwest::blocking::Client::new()
+    .oauth1(secrets)
+    .post("https://api.twitter.com/oauth/request_token")
+    .oauth_callback("oob")
+    .send()
+    .parse_oauth_token()?;
+# Ok(())
+# }
+```
+*/
+
+mod client;
+#[cfg(feature = "multipart")]
+mod multipart;
+mod token_reader;
+
+pub use client::Client;
+#[cfg(feature = "multipart")]
+pub use multipart::MultipartForm;
+pub use token_reader::{TokenReader, TokenReaderBlocking};
+
+/// Compatible interface with reqwest's blocking [`RequestBuilder`](https://docs.rs/reqwest/0.12/reqwest/blocking/struct.RequestBuilder.html).
+pub type RequestBuilder<TSigner> =
+    crate::RequestBuilder<TSigner, reqwest::blocking::RequestBuilder>;
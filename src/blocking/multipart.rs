@@ -0,0 +1,55 @@
+use std::borrow::Cow;
+
+use crate::multipart::impl_multipart_form;
+
+impl_multipart_form!(
+    /**
+    A multipart/form-data body for the blocking `Client`, which records its text parts for the OAuth signing.
+
+    The text parts are not included in the signature by default.
+    Some providers expect them to be signed, then you can call `sign_text_parts`.
+    File parts (or any other parts added with `part`) are never signed.
+
+    # Basic usage
+
+    ```rust
+    use reqwest_oauth1::{blocking::MultipartForm, OAuthClientProvider};
+
+    let consumer_key = "[CONSUMER_KEY]";
+    let consumer_secret = "[CONSUMER_SECRET]";
+    let secrets = reqwest_oauth1::Secrets::new(consumer_key, consumer_secret);
+
+    let form = MultipartForm::new()
+        .text("title", "vacation")
+        .sign_text_parts(true);
+
+    let req = reqwest::blocking::Client::new()
+        .oauth1(secrets)
+        .post("https://example.com/upload")
+        .multipart(form)
+        // and so on...
+        ;
+    ```
+
+    */
+    reqwest::blocking::multipart::Form,
+    reqwest::blocking::multipart::Part
+);
+
+impl MultipartForm {
+    /// Adds a file field.
+    ///
+    /// # Note
+    /// The file is not signed, so the signing fails in the strict mode.
+    pub fn file<T, U>(self, name: T, path: U) -> std::io::Result<Self>
+    where
+        T: Into<Cow<'static, str>>,
+        U: AsRef<std::path::Path>,
+    {
+        Ok(MultipartForm {
+            inner: self.inner.file(name, path)?,
+            has_unsigned_parts: true,
+            ..self
+        })
+    }
+}
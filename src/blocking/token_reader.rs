@@ -0,0 +1,107 @@
+use reqwest::blocking::Response;
+use serde::de::DeserializeOwned;
+
+use crate::{token_reader::read_token_response, Error, Result, TokenReaderResult, TokenResponse};
+
+/// Add parse_oauth_token feature to reqwest::blocking::Response.
+///
+/// The responses with non-2xx status are returned as `TokenReaderError::ErrorResponse`.
+pub trait TokenReader: private::Sealed {
+    fn parse_oauth_token(self) -> Result<TokenResponse>;
+
+    /// Parse the token, and deserialize the other contents into `TExtras`.
+    fn parse_oauth_token_as<TExtras>(self) -> Result<TokenResponse<TExtras>>
+    where
+        TExtras: DeserializeOwned;
+
+    /// Parse the token with your parser, which receives the `Content-Type` and the body,
+    /// e.g. into `TokenResponse<TExtras>` with your `TExtras`.
+    fn parse_oauth_token_with<TExtras, F>(self, parser: F) -> Result<TokenResponse<TExtras>>
+    where
+        F: FnOnce(Option<&str>, &str) -> TokenReaderResult<TokenResponse<TExtras>>;
+}
+
+impl TokenReader for Response {
+    fn parse_oauth_token(self) -> Result<TokenResponse> {
+        self.parse_oauth_token_as()
+    }
+
+    fn parse_oauth_token_as<TExtras>(self) -> Result<TokenResponse<TExtras>>
+    where
+        TExtras: DeserializeOwned,
+    {
+        let status = self.status();
+        let headers = self.headers().clone();
+        let text = self.text()?;
+        read_token_response(status, headers, text, TokenResponse::from_body)
+    }
+
+    fn parse_oauth_token_with<TExtras, F>(self, parser: F) -> Result<TokenResponse<TExtras>>
+    where
+        F: FnOnce(Option<&str>, &str) -> TokenReaderResult<TokenResponse<TExtras>>,
+    {
+        let status = self.status();
+        let headers = self.headers().clone();
+        let text = self.text()?;
+        read_token_response(status, headers, text, parser)
+    }
+}
+
+/// Add parse_oauth_token feature to Result of reqwest::blocking::Response.
+pub trait TokenReaderBlocking: private::SealedWrapper {
+    fn parse_oauth_token(self) -> Result<TokenResponse>;
+
+    /// Parse the token, and deserialize the other contents into `TExtras`.
+    fn parse_oauth_token_as<TExtras>(self) -> Result<TokenResponse<TExtras>>
+    where
+        TExtras: DeserializeOwned;
+
+    /// Parse the token with your parser, which receives the `Content-Type` and the body,
+    /// e.g. into `TokenResponse<TExtras>` with your `TExtras`.
+    fn parse_oauth_token_with<TExtras, F>(self, parser: F) -> Result<TokenResponse<TExtras>>
+    where
+        F: FnOnce(Option<&str>, &str) -> TokenReaderResult<TokenResponse<TExtras>>;
+}
+
+impl<E> TokenReaderBlocking for std::result::Result<Response, E>
+where
+    E: Into<Error>,
+{
+    fn parse_oauth_token(self) -> Result<TokenResponse> {
+        match self {
+            Ok(resp) => resp.parse_oauth_token(),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn parse_oauth_token_as<TExtras>(self) -> Result<TokenResponse<TExtras>>
+    where
+        TExtras: DeserializeOwned,
+    {
+        match self {
+            Ok(resp) => resp.parse_oauth_token_as(),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn parse_oauth_token_with<TExtras, F>(self, parser: F) -> Result<TokenResponse<TExtras>>
+    where
+        F: FnOnce(Option<&str>, &str) -> TokenReaderResult<TokenResponse<TExtras>>,
+    {
+        match self {
+            Ok(resp) => resp.parse_oauth_token_with(parser),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+mod private {
+    use reqwest::blocking::Response;
+
+    use crate::Error;
+
+    pub trait Sealed {}
+    impl Sealed for Response {}
+    pub trait SealedWrapper {}
+    impl<E> SealedWrapper for std::result::Result<Response, E> where E: Into<Error> {}
+}
@@ -0,0 +1,55 @@
+use alloc::string::String;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use sha1::{Digest, Sha1};
+use sha2::{Sha256, Sha512};
+
+use crate::plaintext::PLAINTEXT_METHOD_NAME;
+
+/// Compute the `oauth_body_hash` value of the request body.
+///
+/// The hash algorithm follows the signature method: SHA-256 for HMAC-SHA256,
+/// SHA-512 for HMAC-SHA512, and SHA-1 for the others.
+/// Returns `None` for the PLAINTEXT signature method, that provides no integrity.
+///
+/// cf. https://oauth.googlecode.com/svn/spec/ext/body_hash/1.0/oauth-bodyhash.html
+pub(crate) fn body_hash(signature_method_name: &str, body: &[u8]) -> Option<String> {
+    let digest = match signature_method_name {
+        PLAINTEXT_METHOD_NAME => return None,
+        "HMAC-SHA256" => Sha256::digest(body).to_vec(),
+        "HMAC-SHA512" => Sha512::digest(body).to_vec(),
+        _ => Sha1::digest(body).to_vec(),
+    };
+    Some(STANDARD.encode(digest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::body_hash;
+
+    #[test]
+    fn sha1_body_hash() {
+        // example from the OAuth Request Body Hash specification
+        assert_eq!(
+            body_hash("HMAC-SHA1", b"Hello World!").as_deref(),
+            Some("Lve95gjOVATpfV8EL5X4nxwjKHE=")
+        );
+        assert_eq!(
+            body_hash("RSA-SHA1", b"").as_deref(),
+            Some("2jmj7l5rSw0yVb/vlWAYkK/YBwk=")
+        );
+    }
+
+    #[test]
+    fn sha2_body_hash() {
+        assert_eq!(
+            body_hash("HMAC-SHA256", b"").as_deref(),
+            Some("47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=")
+        );
+    }
+
+    #[test]
+    fn plaintext_has_no_body_hash() {
+        assert_eq!(body_hash("PLAINTEXT", b"Hello World!"), None);
+    }
+}
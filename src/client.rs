@@ -4,21 +4,25 @@
 // please visit their repository: https://github.com/seanmonstar/reqwest .
 // ----------------------------------------------------------------------------
 
-pub use oauth1_request::signature_method::HmacSha1 as DefaultSM;
-use oauth1_request::signature_method::SignatureMethod;
-use reqwest::{IntoUrl, Method};
+use reqwest::IntoUrl;
+use url::Url;
 
-#[cfg(feature = "blocking")]
-use reqwest::blocking::Client as ReqwestClient;
-
-#[cfg(not(feature = "blocking"))]
 use reqwest::Client as ReqwestClient;
 
-use crate::{OAuthParameters, RequestBuilder, SecretsProvider, Signer};
+use crate::{
+    ClockSkew, DefaultSM, Error, ExternalSigner, OAuthParameters, RequestBuilder, SecretsProvider,
+    SignatureMethod, Signer, SigningBackend,
+};
 
 /// Bridge trait from reqwest's `Client` from our `Client`.
+///
+/// It is implemented for `reqwest::Client`, and for `reqwest::blocking::Client`
+/// with the `blocking` feature.
 pub trait OAuthClientProvider {
-    fn oauth1<'a, T>(self, secrets: T) -> Client<Signer<'a, T, DefaultSM>>
+    /// Our client wrapping this reqwest's client, i.e. `Client` or `blocking::Client`.
+    type Client<TSigner>;
+
+    fn oauth1<'a, T>(self, secrets: T) -> Self::Client<Signer<'a, T, DefaultSM>>
     where
         Self: Sized,
         T: SecretsProvider + Clone,
@@ -26,135 +30,281 @@ pub trait OAuthClientProvider {
         self.oauth1_with_params(secrets, OAuthParameters::new())
     }
 
-    fn oauth1_with_params<'a, TSecrets, TSM>(
+    fn oauth1_with_params<TSecrets, TSM>(
         self,
         secrets: TSecrets,
-        params: OAuthParameters<'a, TSM>,
-    ) -> Client<Signer<'a, TSecrets, TSM>>
+        params: OAuthParameters<'_, TSM>,
+    ) -> Self::Client<Signer<'_, TSecrets, TSM>>
     where
         Self: Sized,
         TSecrets: SecretsProvider + Clone,
         TSM: SignatureMethod + Clone;
 }
 
-/// Compatible interface with reqwest's [`Client`](https://docs.rs/reqwest/0.10.8/reqwest/struct.Client.html).
-#[derive(Debug)]
-pub struct Client<TSigner> {
-    inner: ReqwestClient,
-    signer: TSigner,
-}
-
-impl OAuthClientProvider for ReqwestClient {
-    fn oauth1_with_params<'a, TSecrets, TSM>(
+/// Bridge trait from reqwest's `Client` to our `Client` signed with the `SigningBackend`.
+///
+/// It is separated from `OAuthClientProvider`, so the implementors of
+/// `OAuthClientProvider` are not required to support the backends.
+/// The backend signs asynchronously, so it is implemented only for `reqwest::Client`.
+pub trait OAuthBackendProvider: OAuthClientProvider {
+    /// Sign the requests with the `SigningBackend`, instead of the signature method.
+    ///
+    /// The signature method specified in the `params` is ignored.
+    fn oauth1_with_backend<TSecrets, TSM, TBackend>(
         self,
         secrets: TSecrets,
-        parameters: OAuthParameters<'a, TSM>,
-    ) -> Client<Signer<'a, TSecrets, TSM>>
+        params: OAuthParameters<'_, TSM>,
+        backend: TBackend,
+    ) -> Self::Client<ExternalSigner<'_, TSecrets, TBackend>>
     where
         Self: Sized,
         TSecrets: SecretsProvider + Clone,
         TSM: SignatureMethod + Clone,
-    {
-        Client {
-            inner: self,
-            signer: Signer::new(secrets, parameters),
-        }
-    }
+        TBackend: SigningBackend;
 }
 
-impl From<ReqwestClient> for Client<()> {
-    fn from(client: ReqwestClient) -> Self {
-        Client::new_with_client(client)
-    }
-}
+macro_rules! impl_client {
+    ($(#[$meta:meta])* $client:ty, $name:literal, $builder:ident) => {
+        $(#[$meta])*
+        #[derive(Debug)]
+        pub struct Client<TSigner> {
+            inner: $client,
+            signer: TSigner,
+            skew: Option<$crate::ClockSkew>,
+        }
 
-impl Client<()> {
-    /// Constructs a new `Client`.
-    ///
-    /// This method calls reqwest::Client::new() internally.
-    pub fn new() -> Self {
-        Client {
-            inner: ReqwestClient::new(),
-            signer: (),
+        impl $crate::OAuthClientProvider for $client {
+            type Client<TSigner> = Client<TSigner>;
+
+            fn oauth1_with_params<TSecrets, TSM>(
+                self,
+                secrets: TSecrets,
+                parameters: $crate::OAuthParameters<'_, TSM>,
+            ) -> Client<$crate::Signer<'_, TSecrets, TSM>>
+            where
+                Self: Sized,
+                TSecrets: $crate::SecretsProvider + Clone,
+                TSM: $crate::SignatureMethod + Clone,
+            {
+                Client {
+                    inner: self,
+                    signer: $crate::Signer::new(secrets, parameters),
+                    skew: None,
+                }
+            }
         }
-    }
 
-    /// Constructs a new `Client` with specifying inner `reqwest::Client`.
-    pub fn new_with_client(client: ReqwestClient) -> Self {
-        Client {
-            inner: client,
-            signer: (),
+        impl From<$client> for Client<()> {
+            fn from(client: $client) -> Self {
+                Client::new_with_client(client)
+            }
         }
-    }
-}
 
-impl<T> Client<T>
-where
-    T: Clone,
-{
-    /// Convenience method to make a `GET` request to a URL.
-    ///
-    /// # Errors
-    ///
-    /// This method fails whenever supplied `Url` cannot be parsed.
-    pub fn get<U: IntoUrl + Clone>(&self, url: U) -> RequestBuilder<T> {
-        self.request(Method::GET, url)
-    }
+        #[allow(clippy::new_without_default)]
+        impl Client<()> {
+            /// Constructs a new `Client`.
+            ///
+            #[doc = concat!("This method calls ", $name, "::new() internally.")]
+            pub fn new() -> Self {
+                Client {
+                    inner: <$client>::new(),
+                    signer: (),
+                    skew: None,
+                }
+            }
 
-    /// Convenience method to make a `POST` request to a URL.
-    ///
-    /// # Errors
-    ///
-    /// This method fails whenever supplied `Url` cannot be parsed.
-    pub fn post<U: IntoUrl + Clone>(&self, url: U) -> RequestBuilder<T> {
-        self.request(Method::POST, url)
-    }
+            #[doc = concat!("Constructs a new `Client` with specifying inner `", $name, "`.")]
+            pub fn new_with_client(client: $client) -> Self {
+                Client {
+                    inner: client,
+                    signer: (),
+                    skew: None,
+                }
+            }
+        }
 
-    /// Convenience method to make a `PUT` request to a URL.
-    ///
-    /// # Errors
-    ///
-    /// This method fails whenever supplied `Url` cannot be parsed.
-    pub fn put<U: IntoUrl + Clone>(&self, url: U) -> RequestBuilder<T> {
-        self.request(Method::PUT, url)
-    }
+        impl<TSecrets, TSM> Client<$crate::Signer<'_, TSecrets, TSM>>
+        where
+            TSecrets: $crate::SecretsProvider + Clone,
+            TSM: $crate::SignatureMethod + Clone,
+        {
+            /// Generate the signed `GET` URL without sending any request.
+            ///
+            /// The `oauth_*` parameters and `oauth_signature` are added to the query,
+            /// so the URL can be fetched later by another party without the secrets.
+            ///
+            /// # Errors
+            ///
+            /// This method fails if the supplied `Url` cannot be parsed, or the
+            /// signature could not be generated (e.g. with PLAINTEXT, whose signature
+            /// is the secrets).
+            pub fn presigned_url<U: reqwest::IntoUrl + Clone>(
+                &self,
+                url: U,
+            ) -> Result<url::Url, $crate::Error> {
+                self.get(url).presigned_url()
+            }
 
-    /// Convenience method to make a `PATCH` request to a URL.
-    ///
-    /// # Errors
-    ///
-    /// This method fails whenever supplied `Url` cannot be parsed.
-    pub fn patch<U: IntoUrl + Clone>(&self, url: U) -> RequestBuilder<T> {
-        self.request(Method::PATCH, url)
-    }
+            /// Correct the clock skew of the `oauth_timestamp` with the `ClockSkew`.
+            ///
+            /// The offset of the server clock is learned from the responses, and the
+            /// request rejected for its timestamp is signed and sent once more,
+            /// unless its `oauth_timestamp` or `oauth_nonce` is fixed.
+            ///
+            /// # Note
+            /// The `ClockSkew` replaces the `Clock` of the `OAuthParameters`, so create
+            /// it with `ClockSkew::with_clock` to keep your clock.
+            pub fn correct_clock_skew(self, skew: $crate::ClockSkew) -> Self {
+                Client {
+                    inner: self.inner,
+                    signer: self.signer.clock(skew.clone()),
+                    skew: Some(skew),
+                }
+            }
+        }
 
-    /// Convenience method to make a `DELETE` request to a URL.
-    ///
-    /// # Errors
-    ///
-    /// This method fails whenever supplied `Url` cannot be parsed.
-    pub fn delete<U: IntoUrl + Clone>(&self, url: U) -> RequestBuilder<T> {
-        self.request(Method::DELETE, url)
+        impl<T> Client<T>
+        where
+            T: Clone,
+        {
+            /// Convenience method to make a `GET` request to a URL.
+            ///
+            /// # Errors
+            ///
+            /// This method fails whenever supplied `Url` cannot be parsed.
+            pub fn get<U: reqwest::IntoUrl + Clone>(&self, url: U) -> $builder<T> {
+                self.request(reqwest::Method::GET, url)
+            }
+
+            /// Convenience method to make a `POST` request to a URL.
+            ///
+            /// # Errors
+            ///
+            /// This method fails whenever supplied `Url` cannot be parsed.
+            pub fn post<U: reqwest::IntoUrl + Clone>(&self, url: U) -> $builder<T> {
+                self.request(reqwest::Method::POST, url)
+            }
+
+            /// Convenience method to make a `PUT` request to a URL.
+            ///
+            /// # Errors
+            ///
+            /// This method fails whenever supplied `Url` cannot be parsed.
+            pub fn put<U: reqwest::IntoUrl + Clone>(&self, url: U) -> $builder<T> {
+                self.request(reqwest::Method::PUT, url)
+            }
+
+            /// Convenience method to make a `PATCH` request to a URL.
+            ///
+            /// # Errors
+            ///
+            /// This method fails whenever supplied `Url` cannot be parsed.
+            pub fn patch<U: reqwest::IntoUrl + Clone>(&self, url: U) -> $builder<T> {
+                self.request(reqwest::Method::PATCH, url)
+            }
+
+            /// Convenience method to make a `DELETE` request to a URL.
+            ///
+            /// # Errors
+            ///
+            /// This method fails whenever supplied `Url` cannot be parsed.
+            pub fn delete<U: reqwest::IntoUrl + Clone>(&self, url: U) -> $builder<T> {
+                self.request(reqwest::Method::DELETE, url)
+            }
+
+            /// Convenience method to make a `HEAD` request to a URL.
+            ///
+            /// # Errors
+            ///
+            /// This method fails whenever supplied `Url` cannot be parsed.
+            pub fn head<U: reqwest::IntoUrl + Clone>(&self, url: U) -> $builder<T> {
+                self.request(reqwest::Method::HEAD, url)
+            }
+
+            /// Start building a `Request` with the `Method` and `Url`.
+            ///
+            /// Returns a `RequestBuilder<T>`, which will allow setting headers and
+            /// request body before sending.
+            ///
+            /// # Errors
+            ///
+            /// This method fails whenever supplied `Url` cannot be parsed.
+            pub fn request<U: reqwest::IntoUrl + Clone>(
+                &self,
+                method: reqwest::Method,
+                url: U,
+            ) -> $builder<T> {
+                $builder::new(
+                    &self.inner,
+                    method,
+                    url,
+                    self.signer.clone(),
+                    self.skew.clone(),
+                )
+            }
+        }
+    };
+}
+
+pub(crate) use impl_client;
+
+impl_client!(
+    /// Compatible interface with reqwest's [`Client`](https://docs.rs/reqwest/0.10.8/reqwest/struct.Client.html).
+    ReqwestClient,
+    "reqwest::Client",
+    RequestBuilder
+);
+
+impl OAuthBackendProvider for ReqwestClient {
+    fn oauth1_with_backend<TSecrets, TSM, TBackend>(
+        self,
+        secrets: TSecrets,
+        params: OAuthParameters<'_, TSM>,
+        backend: TBackend,
+    ) -> Client<ExternalSigner<'_, TSecrets, TBackend>>
+    where
+        Self: Sized,
+        TSecrets: SecretsProvider + Clone,
+        TSM: SignatureMethod + Clone,
+        TBackend: SigningBackend,
+    {
+        Client {
+            inner: self,
+            signer: ExternalSigner::new(secrets, params, backend),
+            skew: None,
+        }
     }
+}
 
-    /// Convenience method to make a `HEAD` request to a URL.
+impl<TSecrets, TBackend> Client<ExternalSigner<'_, TSecrets, TBackend>>
+where
+    TSecrets: SecretsProvider + Clone,
+    TBackend: SigningBackend,
+{
+    /// Generate the signed `GET` URL with the `SigningBackend`, without
+    /// sending any request.
     ///
     /// # Errors
     ///
-    /// This method fails whenever supplied `Url` cannot be parsed.
-    pub fn head<U: IntoUrl + Clone>(&self, url: U) -> RequestBuilder<T> {
-        self.request(Method::HEAD, url)
+    /// This method fails if the supplied `Url` cannot be parsed, or the
+    /// backend failed to sign.
+    pub async fn presigned_url<U: IntoUrl + Clone>(&self, url: U) -> Result<Url, Error> {
+        self.get(url).presigned_url().await
     }
 
-    /// Start building a `Request` with the `Method` and `Url`.
+    /// Correct the clock skew of the `oauth_timestamp` with the `ClockSkew`.
     ///
-    /// Returns a `RequestBuilder<T>`, which will allow setting headers and
-    /// request body before sending.
+    /// The offset of the server clock is learned from the responses, and the
+    /// request rejected for its timestamp is signed by the backend and sent once more.
     ///
-    /// # Errors
-    ///
-    /// This method fails whenever supplied `Url` cannot be parsed.
-    pub fn request<U: IntoUrl + Clone>(&self, method: Method, url: U) -> RequestBuilder<T> {
-        RequestBuilder::new(&self.inner, method, url, self.signer.clone())
+    /// # Note
+    /// The `ClockSkew` replaces the `Clock` of the `OAuthParameters`, so create
+    /// it with `ClockSkew::with_clock` to keep your clock.
+    pub fn correct_clock_skew(self, skew: ClockSkew) -> Self {
+        Client {
+            inner: self.inner,
+            signer: self.signer.clock(skew.clone()),
+            skew: Some(skew),
+        }
     }
 }
@@ -0,0 +1,52 @@
+use core::fmt::Debug;
+
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/**
+Source of the `oauth_timestamp` value.
+
+The system clock is used by default (with the `std` feature).
+You can replace it with `OAuthParameters::clock`, e.g. to make your tests reproducible.
+
+# Basic usage
+
+```rust
+use reqwest_oauth1::*;
+
+let params = OAuthParameters::new().clock(FixedClock(137_131_201));
+```
+
+*/
+pub trait Clock: Debug + Send + Sync {
+    /// Returns the current UNIX time in seconds.
+    fn now(&self) -> u64;
+}
+
+/// The `Clock` which reads the system time.
+///
+/// The system time before the UNIX epoch is read as `0`.
+///
+/// This struct is available only when the `std` feature is enabled.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs())
+    }
+}
+
+/// The `Clock` which always returns the same UNIX time, for testing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedClock(pub u64);
+
+impl Clock for FixedClock {
+    fn now(&self) -> u64 {
+        self.0
+    }
+}
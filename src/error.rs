@@ -1,13 +1,20 @@
+use alloc::string::String;
+use core::fmt::{self, Display};
+
+#[cfg(feature = "reqwest")]
 use thiserror::Error;
 
 /// Result type bound with `Error`.
-pub type Result<T> = std::result::Result<T, Error>;
+#[cfg(feature = "reqwest")]
+pub type Result<T> = core::result::Result<T, Error>;
 /// Result type bound with `SignError`.
-pub type SignResult<T> = std::result::Result<T, SignerError>;
+pub type SignResult<T> = core::result::Result<T, SignerError>;
 /// Result type bound with `TokenReaderError`.
-pub type TokenReaderResult<T> = std::result::Result<T, TokenReaderError>;
+#[cfg(feature = "reqwest")]
+pub type TokenReaderResult<T> = core::result::Result<T, TokenReaderError>;
 
 /// The Error bundles the TokenReaderError, SignError, and reqwest::Error.
+#[cfg(feature = "reqwest")]
 #[derive(Error, Debug)]
 pub enum Error {
     /// Represents TokenReaderError
@@ -19,29 +26,134 @@ pub enum Error {
     /// Represents reqwest::Error
     #[error("request failed : {0}")]
     Reqwest(#[from] reqwest::Error),
+    /// Represents the `oauth_problem` reported by the provider
+    #[error("OAuth problem reported : {}", .0.problem)]
+    Problem(Box<crate::ProblemReport>),
 }
 
 /// Errors about the signing with OAuth1 protocol.
-#[derive(Error, Debug, Clone)]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum SignerError {
     /// Specified oauth_* parameter is not existed in the protocol specification.
-    #[error("unknown oauth parameter : {0}")]
+    ///
+    /// No longer returned, such parameters are signed as the extension parameters.
+    #[deprecated(
+        note = "never returned, the unknown oauth_* parameters are signed as the extension parameters"
+    )]
     UnknownParameter(String),
     /// Specified oauth_* parameter is not configured via the reqwest::RequestBuilder::(query/form).
-    #[error("specified parameter {0} could not be configured via the reqwest parameters.")]
     UnconfigurableParameter(String),
     /// An invalid value is specified as the oauth_timestamp parameter.
-    #[error("invalid oauth_timestamp, must be u64, but {0} is not compatible.")]
     InvalidTimestamp(String),
     /// An invalid value is specified as the oauth_version parameter.
-    #[error("invalid oauth_version, must be 1.0 or just empty, but specified {0}.")]
     InvalidVersion(String),
+    /// The private key for the signature method could not be loaded.
+    InvalidPrivateKey(String),
+    /// The PLAINTEXT signature method is used with the request not sent over https.
+    ///
+    /// Only the scheme and the host of the URL are reported.
+    InsecureTransport(String),
+    /// The signing backend failed to sign the signature base string.
+    Backend(String),
+    /// The OAuth parameters could not be transmitted in the form body,
+    /// because the request has a non-form body.
+    FormBodyUnavailable,
+    /// The request URL could not be parsed.
+    InvalidUrl,
+    /// The request body could not be read to sign, e.g. a stream.
+    UnreadableBody,
+    /// The parameter must be specified, because it could not be generated
+    /// without the `std` feature.
+    MissingParameter(&'static str),
+    /// The `oauth_*` parameter is sent without being signed,
+    /// e.g. with `query_without_capture` (only in the strict mode).
+    UnsignedParameter(String),
+    /// The request body is not covered by the signature, e.g. a multipart body
+    /// with the file parts, or the body without the `oauth_body_hash` (only in the strict mode).
+    UnsignedBody,
+    /// The request could not be pre-signed: the pre-signed URL is only for the
+    /// `GET` request without the body, and the PLAINTEXT signature would reveal the secrets.
+    UnpresignableRequest(String),
 }
 
+// implemented without thiserror, to be available without the `std` feature
+impl Display for SignerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[allow(deprecated)]
+            SignerError::UnknownParameter(v) => write!(f, "unknown oauth parameter : {}", v),
+            SignerError::UnconfigurableParameter(v) => write!(
+                f,
+                "specified parameter {} could not be configured via the reqwest parameters.",
+                v
+            ),
+            SignerError::InvalidTimestamp(v) => write!(
+                f,
+                "invalid oauth_timestamp, must be u64, but {} is not compatible.",
+                v
+            ),
+            SignerError::InvalidVersion(v) => write!(
+                f,
+                "invalid oauth_version, must be 1.0 or just empty, but specified {}.",
+                v
+            ),
+            SignerError::InvalidPrivateKey(v) => write!(f, "invalid private key : {}", v),
+            SignerError::InsecureTransport(v) => write!(
+                f,
+                "PLAINTEXT signature method requires https, but the request is sent to {}.",
+                v
+            ),
+            SignerError::Backend(v) => write!(f, "signing backend failed : {}", v),
+            SignerError::FormBodyUnavailable => write!(
+                f,
+                "OAuth parameters could not be sent in the form body, the request body is not an urlencoded form."
+            ),
+            SignerError::InvalidUrl => write!(f, "the request URL could not be parsed."),
+            SignerError::UnreadableBody => write!(
+                f,
+                "the request body could not be read to sign, e.g. a stream."
+            ),
+            SignerError::MissingParameter(v) => write!(
+                f,
+                "{} must be specified without the std feature.",
+                v
+            ),
+            SignerError::UnsignedParameter(v) => write!(
+                f,
+                "{} is sent without being signed, that is refused in the strict mode.",
+                v
+            ),
+            SignerError::UnsignedBody => write!(
+                f,
+                "the request body is not covered by the signature, that is refused in the strict mode."
+            ),
+            SignerError::UnpresignableRequest(v) => {
+                write!(f, "the request could not be pre-signed : {}", v)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SignerError {}
+
 /// Errors thrown from token_reader.
+#[cfg(feature = "reqwest")]
 #[derive(Error, Debug, Clone)]
 pub enum TokenReaderError {
     /// Returned value could not be parsed in the TokenReader.
     #[error("the response has malformed format: key {0} is not found in response {1}")]
     TokenKeyNotFound(&'static str, String),
+    /// Returned value could not be parsed, e.g. an invalid JSON.
+    #[error("the response has malformed format: {0}")]
+    MalformedResponse(String),
+    /// The request token response has `oauth_callback_confirmed` with the value other than `true`.
+    ///
+    /// `OAuth1Flow` also refuses the response without it, with the empty value.
+    #[error("the provider did not confirm the callback: oauth_callback_confirmed={0}")]
+    CallbackNotConfirmed(String),
+    /// The provider responded with non-2xx status.
+    #[error("the provider responded with the error status {}", .0.status)]
+    ErrorResponse(Box<crate::ErrorResponse>),
 }
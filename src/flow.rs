@@ -0,0 +1,419 @@
+use std::{collections::HashMap, fmt};
+
+use reqwest::Client as ReqwestClient;
+use serde::de::DeserializeOwned;
+use url::Url;
+
+use crate::{
+    DefaultSM, OAuthClientProvider, OAuthParameters, Result, Secrets, SignatureMethod, SignerError,
+    TokenReader, TokenReaderError,
+};
+
+const OAUTH_TOKEN_KEY: &str = "oauth_token";
+
+const OAUTH_CALLBACK_CONFIRMED_KEY: &str = "oauth_callback_confirmed";
+
+/**
+The three-legged OAuth 1.0a flow: acquire the request token, let the user
+authorize it, and exchange it for the access token.
+
+cf. [RFC 5849 section 2](https://tools.ietf.org/html/rfc5849#section-2)
+
+# Basic usage
+
+```rust
+use reqwest_oauth1::{OAuth1Flow, OAuthClientProvider};
+
+async fn authorize() -> reqwest_oauth1::Result<()> {
+    let flow = OAuth1Flow::new(
+        "[CONSUMER_KEY]",
+        "[CONSUMER_SECRET]",
+        "https://api.twitter.com/oauth/request_token",
+        "https://api.twitter.com/oauth/authorize",
+        "https://api.twitter.com/oauth/access_token",
+    );
+
+    // step 1: acquire the request token
+    let request_token = flow.request_token("oob").await?;
+
+    // step 2: let the user authorize the request token, and get the verifier
+    let url = flow.authorize_url(&request_token, &[("force_login", "true")])?;
+    println!("please access to: {}", url);
+    let pin = "[PIN]";
+
+    // step 3: exchange the request token for the access token
+    let access_token = flow.access_token(&request_token, pin).await?;
+    let resp = reqwest::Client::new()
+        .oauth1(access_token.secrets)
+        .get("https://api.twitter.com/1.1/account/verify_credentials.json")
+        .send()
+        .await?;
+    Ok(())
+}
+```
+
+*/
+#[derive(Clone)]
+pub struct OAuth1Flow<'a, TSM = DefaultSM>
+where
+    TSM: SignatureMethod + Clone,
+{
+    client: ReqwestClient,
+    consumer_key: String,
+    consumer_secret: String,
+    parameters: OAuthParameters<'a, TSM>,
+    request_token_url: String,
+    authorize_url: String,
+    access_token_url: String,
+}
+
+/// Represents the request token, which is authorized by the user.
+#[derive(Clone)]
+pub struct RequestToken {
+    /// OAuth Token
+    pub oauth_token: String,
+    /// OAuth Token Secret
+    pub oauth_token_secret: String,
+    /// Other contents
+    pub remain: HashMap<String, String>,
+}
+
+/// Represents the access token, with the secrets to sign the requests.
+#[derive(Clone)]
+pub struct AccessToken<TExtras = HashMap<String, String>> {
+    /// OAuth Token
+    pub oauth_token: String,
+    /// OAuth Token Secret
+    pub oauth_token_secret: String,
+    /// Other contents, e.g. `user_id`
+    pub remain: TExtras,
+    /// The consumer key and secret with the access token, to sign the requests
+    pub secrets: Secrets<'static>,
+}
+
+// never print the secrets
+impl<TSM> fmt::Debug for OAuth1Flow<'_, TSM>
+where
+    TSM: SignatureMethod + Clone + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OAuth1Flow")
+            .field("client", &self.client)
+            .field("consumer_key", &self.consumer_key)
+            .field("consumer_secret", &format_args!("[REDACTED]"))
+            .field("parameters", &self.parameters)
+            .field("request_token_url", &self.request_token_url)
+            .field("authorize_url", &self.authorize_url)
+            .field("access_token_url", &self.access_token_url)
+            .finish()
+    }
+}
+
+impl fmt::Debug for RequestToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestToken")
+            .field("oauth_token", &self.oauth_token)
+            .field("oauth_token_secret", &format_args!("[REDACTED]"))
+            .field("remain", &self.remain)
+            .finish()
+    }
+}
+
+impl<TExtras> fmt::Debug for AccessToken<TExtras>
+where
+    TExtras: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AccessToken")
+            .field("oauth_token", &self.oauth_token)
+            .field("oauth_token_secret", &format_args!("[REDACTED]"))
+            .field("remain", &self.remain)
+            .field("secrets", &format_args!("[REDACTED]"))
+            .finish()
+    }
+}
+
+impl OAuth1Flow<'static, DefaultSM> {
+    /// Create the flow with the consumer key and secret, and the three endpoints.
+    pub fn new<TKey, TSecret>(
+        consumer_key: TKey,
+        consumer_secret: TSecret,
+        request_token_url: &str,
+        authorize_url: &str,
+        access_token_url: &str,
+    ) -> Self
+    where
+        TKey: Into<String>,
+        TSecret: Into<String>,
+    {
+        OAuth1Flow {
+            client: ReqwestClient::new(),
+            consumer_key: consumer_key.into(),
+            consumer_secret: consumer_secret.into(),
+            parameters: OAuthParameters::new(),
+            request_token_url: request_token_url.to_string(),
+            authorize_url: authorize_url.to_string(),
+            access_token_url: access_token_url.to_string(),
+        }
+    }
+}
+
+impl<'a, TSM> OAuth1Flow<'a, TSM>
+where
+    TSM: SignatureMethod + Clone,
+{
+    /// set the reqwest's `Client` to send the token requests.
+    pub fn client(self, client: ReqwestClient) -> Self {
+        OAuth1Flow { client, ..self }
+    }
+
+    /// set the parameters to sign the token requests, e.g. the signature method.
+    pub fn parameters<T>(self, parameters: OAuthParameters<'a, T>) -> OAuth1Flow<'a, T>
+    where
+        T: SignatureMethod + Clone,
+    {
+        OAuth1Flow {
+            client: self.client,
+            consumer_key: self.consumer_key,
+            consumer_secret: self.consumer_secret,
+            parameters,
+            request_token_url: self.request_token_url,
+            authorize_url: self.authorize_url,
+            access_token_url: self.access_token_url,
+        }
+    }
+
+    /// Acquire the request token with the `oauth_callback` (or `"oob"`).
+    ///
+    /// The response must have `oauth_callback_confirmed=true`,
+    /// otherwise `TokenReaderError::CallbackNotConfirmed` is returned.
+    ///
+    /// # Note
+    /// Only the flow refuses the response without `oauth_callback_confirmed`
+    /// (with the empty value in the error). `parse_oauth_token` accepts it,
+    /// as the OAuth 1.0 providers do not send the parameter.
+    pub async fn request_token(&self, callback: &str) -> Result<RequestToken> {
+        let resp = self
+            .client
+            .clone()
+            .oauth1_with_params(self.consumer_secrets(), self.parameters.clone())
+            .post(&self.request_token_url)
+            .oauth_callback(callback.to_string())
+            .send()
+            .await?
+            .parse_oauth_token()
+            .await?;
+        // the value other than `true` is refused in `parse_oauth_token`,
+        // but the missing one is refused only here
+        if !resp.remain.contains_key(OAUTH_CALLBACK_CONFIRMED_KEY) {
+            return Err(TokenReaderError::CallbackNotConfirmed(String::new()).into());
+        }
+        Ok(RequestToken {
+            oauth_token: resp.oauth_token,
+            oauth_token_secret: resp.oauth_token_secret,
+            remain: resp.remain,
+        })
+    }
+
+    /// The URL to let the user authorize the request token,
+    /// with the extra query parameters (e.g. `force_login`).
+    pub fn authorize_url(
+        &self,
+        token: &RequestToken,
+        extra_params: &[(&str, &str)],
+    ) -> Result<Url> {
+        let mut url = Url::parse(&self.authorize_url).map_err(|_| SignerError::InvalidUrl)?;
+        url.query_pairs_mut()
+            .append_pair(OAUTH_TOKEN_KEY, &token.oauth_token)
+            .extend_pairs(extra_params);
+        Ok(url)
+    }
+
+    /// Exchange the authorized request token for the access token with the `oauth_verifier`.
+    pub async fn access_token(&self, token: &RequestToken, verifier: &str) -> Result<AccessToken> {
+        self.access_token_as(token, verifier).await
+    }
+
+    /// Exchange the authorized request token for the access token,
+    /// and deserialize the other contents into `TExtras`.
+    pub async fn access_token_as<TExtras>(
+        &self,
+        token: &RequestToken,
+        verifier: &str,
+    ) -> Result<AccessToken<TExtras>>
+    where
+        TExtras: DeserializeOwned,
+    {
+        let secrets = self
+            .consumer_secrets()
+            .token(token.oauth_token.clone(), token.oauth_token_secret.clone());
+        let resp = self
+            .client
+            .clone()
+            .oauth1_with_params(secrets, self.parameters.clone())
+            .post(&self.access_token_url)
+            .oauth_verifier(verifier.to_string())
+            .send()
+            .await?
+            .parse_oauth_token_as::<TExtras>()
+            .await?;
+        let secrets = self
+            .consumer_secrets()
+            .token(resp.oauth_token.clone(), resp.oauth_token_secret.clone());
+        Ok(AccessToken {
+            oauth_token: resp.oauth_token,
+            oauth_token_secret: resp.oauth_token_secret,
+            remain: resp.remain,
+            secrets,
+        })
+    }
+
+    fn consumer_secrets(&self) -> Secrets<'static> {
+        Secrets::new(self.consumer_key.clone(), self.consumer_secret.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SecretsProvider;
+
+    #[test]
+    fn build_authorize_url() {
+        let flow = OAuth1Flow::new(
+            "dpf43f3p2l4k3l03",
+            "kd94hf93k423kf44",
+            "https://photos.example.net/initiate",
+            "https://photos.example.net/authorize?lang=en",
+            "https://photos.example.net/token",
+        );
+        let token = RequestToken {
+            oauth_token: "hh5s93j4hdidpola".to_string(),
+            oauth_token_secret: "hdhd0244k9j7ao03".to_string(),
+            remain: HashMap::new(),
+        };
+
+        let url = flow.authorize_url(&token, &[]).unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://photos.example.net/authorize?lang=en&oauth_token=hh5s93j4hdidpola"
+        );
+
+        let url = flow
+            .authorize_url(
+                &token,
+                &[("force_login", "true"), ("screen_name", "Jane Doe")],
+            )
+            .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://photos.example.net/authorize?lang=en&oauth_token=hh5s93j4hdidpola&force_login=true&screen_name=Jane+Doe"
+        );
+    }
+
+    #[tokio::test]
+    async fn acquire_access_token() {
+        let responses = [
+            "oauth_token=hh5s93j4hdidpola&oauth_token_secret=hdhd0244k9j7ao03&oauth_callback_confirmed=true",
+            "oauth_token=nnch734d00sl2jdk&oauth_token_secret=pfkkdhi9sl3r4s00&user_id=6253282",
+        ]
+        .iter()
+        .map(|body| {
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/x-www-form-urlencoded\r\n\
+                 Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+        })
+        .collect();
+        let (endpoint, server) = crate::test_server::serve(responses).await;
+
+        let flow = OAuth1Flow::new(
+            "dpf43f3p2l4k3l03",
+            "kd94hf93k423kf44",
+            &format!("{}/initiate", endpoint),
+            &format!("{}/authorize", endpoint),
+            &format!("{}/token", endpoint),
+        )
+        .client(ReqwestClient::builder().no_proxy().build().unwrap());
+
+        let request_token = flow
+            .request_token("http://printer.example.com/ready")
+            .await
+            .unwrap();
+        assert_eq!(request_token.oauth_token, "hh5s93j4hdidpola");
+        assert_eq!(request_token.oauth_token_secret, "hdhd0244k9j7ao03");
+
+        #[derive(serde::Deserialize)]
+        struct Extras {
+            user_id: u64,
+        }
+        let access_token = flow
+            .access_token_as::<Extras>(&request_token, "hfdp7dh39dks9884")
+            .await
+            .unwrap();
+        assert_eq!(access_token.oauth_token, "nnch734d00sl2jdk");
+        assert_eq!(access_token.remain.user_id, 6253282);
+        assert_eq!(
+            access_token.secrets.get_token_pair_option(),
+            Some(("nnch734d00sl2jdk", "pfkkdhi9sl3r4s00"))
+        );
+
+        let requests = server.await.unwrap();
+        assert!(requests[0].starts_with("POST /initiate "));
+        assert!(requests[0].contains("oauth_callback=\"http%3A%2F%2Fprinter.example.com%2Fready\""));
+        assert!(requests[1].starts_with("POST /token "));
+        assert!(requests[1].contains("oauth_token=\"hh5s93j4hdidpola\""));
+        assert!(requests[1].contains("oauth_verifier=\"hfdp7dh39dks9884\""));
+    }
+
+    #[test]
+    fn redact_secrets_in_debug() {
+        let flow = OAuth1Flow::new(
+            "dpf43f3p2l4k3l03",
+            "kd94hf93k423kf44",
+            "https://photos.example.net/initiate",
+            "https://photos.example.net/authorize",
+            "https://photos.example.net/token",
+        );
+        let debug = format!("{:?}", flow);
+        assert!(debug.contains("dpf43f3p2l4k3l03"));
+        assert!(!debug.contains("kd94hf93k423kf44"));
+
+        let token = RequestToken {
+            oauth_token: "hh5s93j4hdidpola".to_string(),
+            oauth_token_secret: "hdhd0244k9j7ao03".to_string(),
+            remain: HashMap::new(),
+        };
+        let debug = format!("{:?}", token);
+        assert!(debug.contains("hh5s93j4hdidpola"));
+        assert!(!debug.contains("hdhd0244k9j7ao03"));
+
+        let token = AccessToken {
+            oauth_token: "nnch734d00sl2jdk".to_string(),
+            oauth_token_secret: "pfkkdhi9sl3r4s00".to_string(),
+            remain: HashMap::<String, String>::new(),
+            secrets: Secrets::new("dpf43f3p2l4k3l03", "kd94hf93k423kf44")
+                .token("nnch734d00sl2jdk", "pfkkdhi9sl3r4s00"),
+        };
+        let debug = format!("{:?}", token);
+        assert!(debug.contains("nnch734d00sl2jdk"));
+        assert!(!debug.contains("pfkkdhi9sl3r4s00"));
+        assert!(!debug.contains("kd94hf93k423kf44"));
+    }
+
+    #[test]
+    fn refuse_invalid_authorize_url() {
+        let flow = OAuth1Flow::new("key", "secret", "initiate", "authorize", "token");
+        let token = RequestToken {
+            oauth_token: "hh5s93j4hdidpola".to_string(),
+            oauth_token_secret: "hdhd0244k9j7ao03".to_string(),
+            remain: HashMap::new(),
+        };
+        assert!(matches!(
+            flow.authorize_url(&token, &[]),
+            Err(crate::Error::Signer(SignerError::InvalidUrl))
+        ));
+    }
+}
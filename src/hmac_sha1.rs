@@ -0,0 +1,129 @@
+use alloc::{format, vec::Vec};
+use core::fmt::{self, Display, Write};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+
+use crate::util::percent_encode;
+use crate::{Sign, SignatureMethod};
+
+/**
+The `HMAC-SHA1` signature method, used by default.
+
+cf. https://tools.ietf.org/html/rfc5849#section-3.4.2
+*/
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HmacSha1;
+
+/// A type that signs a signature base string with the HMAC-SHA1 signature algorithm.
+#[derive(Debug, Clone)]
+pub struct HmacSha1Sign {
+    mac: Hmac<Sha1>,
+}
+
+/// A signature produced by an `HmacSha1Sign`.
+#[derive(Debug, Clone)]
+pub struct HmacSha1Signature(Vec<u8>);
+
+struct MacWrite<'a>(&'a mut Hmac<Sha1>);
+
+impl Write for MacWrite<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.update(s.as_bytes());
+        Ok(())
+    }
+}
+
+impl SignatureMethod for HmacSha1 {
+    type Sign = HmacSha1Sign;
+
+    fn sign_with(
+        self,
+        consumer_secret: impl Display,
+        token_secret: Option<impl Display>,
+    ) -> HmacSha1Sign {
+        // secrets are already percent-encoded by the signer
+        let key = match token_secret {
+            Some(ts) => format!("{}&{}", consumer_secret, ts),
+            None => format!("{}&", consumer_secret),
+        };
+        HmacSha1Sign {
+            // HMAC can take a key of any size
+            mac: Hmac::new_from_slice(key.as_bytes()).unwrap(),
+        }
+    }
+}
+
+impl Sign for HmacSha1Sign {
+    type Signature = HmacSha1Signature;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        "HMAC-SHA1"
+    }
+
+    fn request_method(&mut self, method: &str) {
+        self.mac.update(method.as_bytes());
+        self.mac.update(b"&");
+    }
+
+    fn uri(&mut self, uri: impl Display) {
+        write!(MacWrite(&mut self.mac), "{}&", uri).unwrap();
+    }
+
+    fn parameter(&mut self, key: &str, value: impl Display) {
+        self.mac.update(key.as_bytes());
+        self.mac.update(b"%3D"); // '='
+        write!(MacWrite(&mut self.mac), "{}", value).unwrap();
+    }
+
+    fn delimiter(&mut self) {
+        self.mac.update(b"%26"); // '&'
+    }
+
+    fn finish(self) -> HmacSha1Signature {
+        HmacSha1Signature(self.mac.finalize().into_bytes().to_vec())
+    }
+}
+
+impl Display for HmacSha1Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&percent_encode(&STANDARD.encode(&self.0)), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn rfc5849_signature() {
+        // https://tools.ietf.org/html/rfc5849#section-3.4.1.1
+        let mut sign = HmacSha1.sign_with("kd94hf93k423kf44", Some("pfkkdhi9sl3r4s00"));
+        sign.request_method("GET");
+        sign.uri("http%3A%2F%2Fphotos.example.net%2Fphotos");
+        let params = [
+            ("file", "vacation.jpg"),
+            ("oauth_consumer_key", "dpf43f3p2l4k3l03"),
+            ("oauth_nonce", "kllo9940pd9333jh"),
+            ("oauth_signature_method", "HMAC-SHA1"),
+            ("oauth_timestamp", "1191242096"),
+            ("oauth_token", "nnch734d00sl2jdk"),
+            ("oauth_version", "1.0"),
+            ("size", "original"),
+        ];
+        for (i, (key, value)) in params.iter().enumerate() {
+            if i > 0 {
+                sign.delimiter();
+            }
+            sign.parameter(key, value);
+        }
+        assert_eq!(sign.get_signature_method_name(), "HMAC-SHA1");
+        assert_eq!(
+            sign.finish().to_string(),
+            "tR3%2BTy81lMeYAr%2FFid0kMTYa%2FWM%3D"
+        );
+    }
+}
@@ -0,0 +1,260 @@
+use alloc::{format, string::String, vec::Vec};
+use core::fmt::{self, Display, Write};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use hmac::{Hmac, Mac};
+use sha2::{Sha256, Sha512};
+
+use crate::util::percent_encode;
+use crate::{Sign, SignatureMethod};
+
+/**
+The `HMAC-SHA256` signature method.
+
+This method is not defined in RFC 5849, but it is required by some providers
+(e.g. NetSuite Token-Based Authentication).
+The signature is generated in the same manner as `HMAC-SHA1`, except for the digest algorithm.
+
+# Basic usage
+
+```rust
+use reqwest_oauth1::*;
+
+use reqwest::Client;
+
+let consumer_key = "[CONSUMER_KEY]";
+let consumer_secret = "[CONSUMER_SECRET]";
+let secrets = reqwest_oauth1::Secrets::new(consumer_key, consumer_secret);
+
+let params = reqwest_oauth1::OAuthParameters::new()
+    .signature_method(HmacSha256);
+
+let req = Client::new()
+    .oauth1_with_params(secrets, params)
+    .get("https://example.com/")
+    // and so on...
+    ;
+```
+
+*/
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HmacSha256;
+
+/// The `HMAC-SHA512` signature method.
+///
+/// The signature is generated in the same manner as `HMAC-SHA1`, except for the digest algorithm.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HmacSha512;
+
+/// A type that signs a signature base string with the HMAC-SHA256 or HMAC-SHA512 signature algorithm.
+#[derive(Debug, Clone)]
+pub struct HmacSha2Sign {
+    mac: HmacSha2,
+}
+
+/// A signature produced by an `HmacSha2Sign`.
+#[derive(Debug, Clone)]
+pub struct HmacSha2Signature(Vec<u8>);
+
+#[derive(Debug, Clone)]
+enum HmacSha2 {
+    Sha256(Hmac<Sha256>),
+    Sha512(Hmac<Sha512>),
+}
+
+impl HmacSha2 {
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            HmacSha2::Sha256(mac) => mac.update(data),
+            HmacSha2::Sha512(mac) => mac.update(data),
+        }
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        match self {
+            HmacSha2::Sha256(mac) => mac.finalize().into_bytes().to_vec(),
+            HmacSha2::Sha512(mac) => mac.finalize().into_bytes().to_vec(),
+        }
+    }
+}
+
+impl Write for HmacSha2 {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.update(s.as_bytes());
+        Ok(())
+    }
+}
+
+fn signing_key(consumer_secret: impl Display, token_secret: Option<impl Display>) -> String {
+    // secrets are already percent-encoded by the signer
+    match token_secret {
+        Some(ts) => format!("{}&{}", consumer_secret, ts),
+        None => format!("{}&", consumer_secret),
+    }
+}
+
+impl SignatureMethod for HmacSha256 {
+    type Sign = HmacSha2Sign;
+
+    fn sign_with(
+        self,
+        consumer_secret: impl Display,
+        token_secret: Option<impl Display>,
+    ) -> HmacSha2Sign {
+        let key = signing_key(consumer_secret, token_secret);
+        HmacSha2Sign {
+            // HMAC can take a key of any size
+            mac: HmacSha2::Sha256(Hmac::new_from_slice(key.as_bytes()).unwrap()),
+        }
+    }
+}
+
+impl SignatureMethod for HmacSha512 {
+    type Sign = HmacSha2Sign;
+
+    fn sign_with(
+        self,
+        consumer_secret: impl Display,
+        token_secret: Option<impl Display>,
+    ) -> HmacSha2Sign {
+        let key = signing_key(consumer_secret, token_secret);
+        HmacSha2Sign {
+            // HMAC can take a key of any size
+            mac: HmacSha2::Sha512(Hmac::new_from_slice(key.as_bytes()).unwrap()),
+        }
+    }
+}
+
+impl Sign for HmacSha2Sign {
+    type Signature = HmacSha2Signature;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        match self.mac {
+            HmacSha2::Sha256(_) => "HMAC-SHA256",
+            HmacSha2::Sha512(_) => "HMAC-SHA512",
+        }
+    }
+
+    fn request_method(&mut self, method: &str) {
+        self.mac.update(method.as_bytes());
+        self.mac.update(b"&");
+    }
+
+    fn uri(&mut self, uri: impl Display) {
+        write!(self.mac, "{}&", uri).unwrap();
+    }
+
+    fn parameter(&mut self, key: &str, value: impl Display) {
+        self.mac.update(key.as_bytes());
+        self.mac.update(b"%3D"); // '='
+        write!(self.mac, "{}", value).unwrap();
+    }
+
+    fn delimiter(&mut self) {
+        self.mac.update(b"%26"); // '&'
+    }
+
+    fn finish(self) -> HmacSha2Signature {
+        HmacSha2Signature(self.mac.finalize())
+    }
+}
+
+impl Display for HmacSha2Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&percent_encode(&STANDARD.encode(&self.0)), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OAuthParameters, Secrets, Signer};
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().fold(String::new(), |mut s, b| {
+            write!(s, "{:02x}", b).unwrap();
+            s
+        })
+    }
+
+    #[test]
+    fn rfc4231_test_case_2() {
+        // https://tools.ietf.org/html/rfc4231#section-4.3
+        let data = b"what do ya want for nothing?";
+
+        let mut mac = HmacSha2::Sha256(Hmac::new_from_slice(b"Jefe").unwrap());
+        mac.update(data);
+        assert_eq!(
+            hex(&mac.finalize()),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+
+        let mut mac = HmacSha2::Sha512(Hmac::new_from_slice(b"Jefe").unwrap());
+        mac.update(data);
+        assert_eq!(
+            hex(&mac.finalize()),
+            "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554\
+             9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"
+        );
+    }
+
+    #[test]
+    fn sign_request_with_signer() {
+        // the request of https://tools.ietf.org/html/rfc5849#section-1.2
+        // expected values are computed with openssl
+        let secrets = Secrets::new("dpf43f3p2l4k3l03", "kd94hf93k423kf44")
+            .token("nnch734d00sl2jdk", "pfkkdhi9sl3r4s00");
+        let url = "http://photos.example.net/photos?file=vacation.jpg&size=original";
+
+        let params = OAuthParameters::new()
+            .signature_method(HmacSha256)
+            .nonce("chapoH")
+            .timestamp(137_131_202u64);
+        let signed = Signer::new(secrets.clone(), params)
+            .sign("GET", url, &[] as &[(&str, &str)])
+            .unwrap();
+        assert_eq!(
+            signed.authorization_header(),
+            "OAuth oauth_consumer_key=\"dpf43f3p2l4k3l03\",oauth_nonce=\"chapoH\",\
+             oauth_signature_method=\"HMAC-SHA256\",oauth_timestamp=\"137131202\",\
+             oauth_token=\"nnch734d00sl2jdk\",\
+             oauth_signature=\"HtMwoX2zenlFjgGg%2FSNEoKEQmL7CzxYFEKzs7er044Y%3D\""
+        );
+
+        let params = OAuthParameters::new()
+            .signature_method(HmacSha512)
+            .nonce("chapoH")
+            .timestamp(137_131_202u64);
+        let signed = Signer::new(secrets, params)
+            .sign("GET", url, &[] as &[(&str, &str)])
+            .unwrap();
+        assert_eq!(
+            signed.authorization_header(),
+            "OAuth oauth_consumer_key=\"dpf43f3p2l4k3l03\",oauth_nonce=\"chapoH\",\
+             oauth_signature_method=\"HMAC-SHA512\",oauth_timestamp=\"137131202\",\
+             oauth_token=\"nnch734d00sl2jdk\",\
+             oauth_signature=\"GnPni%2FI%2F%2FSEqvsTDz9Hl%2FoqxAlzMUgeQVrspr%2BN1EWltelChqWWuhrg\
+             ewHZy90k8K2weeJkkURa%2FW10NRXY7uQ%3D%3D\""
+        );
+    }
+
+    #[test]
+    fn signing_key_is_concatenated() {
+        let mut expected =
+            HmacSha2::Sha256(Hmac::new_from_slice(b"kd94hf93k423kf44&pfkkdhi9sl3r4s00").unwrap());
+        expected.update(b"GET&");
+
+        let mut sign = HmacSha256.sign_with("kd94hf93k423kf44", Some("pfkkdhi9sl3r4s00"));
+        sign.request_method("GET");
+        assert_eq!(sign.get_signature_method_name(), "HMAC-SHA256");
+        assert_eq!(sign.mac.finalize(), expected.finalize());
+
+        let mut expected = HmacSha2::Sha512(Hmac::new_from_slice(b"kd94hf93k423kf44&").unwrap());
+        expected.update(b"GET&");
+
+        let mut sign = HmacSha512.sign_with("kd94hf93k423kf44", None::<&str>);
+        sign.request_method("GET");
+        assert_eq!(sign.get_signature_method_name(), "HMAC-SHA512");
+        assert_eq!(sign.mac.finalize(), expected.finalize());
+    }
+}
@@ -0,0 +1,188 @@
+use std::{convert::TryFrom, fmt, time::Duration};
+
+use http::Method;
+use reqwest::{header::HeaderMap, header::HeaderName, header::HeaderValue, IntoUrl};
+use serde::Serialize;
+use url::Url;
+
+#[cfg(feature = "multipart")]
+use crate::multipart::CapturedParts;
+
+/**
+The request builder of reqwest, which is wrapped by our `RequestBuilder`.
+
+This trait is implemented for `reqwest::RequestBuilder`, and for
+`reqwest::blocking::RequestBuilder` with the `blocking` feature,
+so the asynchronous and the blocking API share the OAuth signing.
+
+This trait is sealed.
+*/
+pub trait InnerRequestBuilder: fmt::Debug + Sized + private::Sealed {
+    /// The reqwest's `Client` which creates the request builder.
+    type Client;
+    /// The request body.
+    type Body: From<String>;
+    /// The multipart/form-data body, which records its text parts.
+    #[cfg(feature = "multipart")]
+    type MultipartForm;
+
+    /// Start building the request with the `Method` and `Url`.
+    fn request<U: IntoUrl>(client: &Self::Client, method: Method, url: U) -> Self;
+
+    /// Modify the query string of the URL.
+    fn query<T: Serialize + ?Sized>(self, query: &T) -> Self;
+
+    /// Send a form body.
+    fn form<T: Serialize + ?Sized>(self, form: &T) -> Self;
+
+    /// Send a JSON body.
+    #[cfg(feature = "json")]
+    fn json<T: Serialize + ?Sized>(self, json: &T) -> Self;
+
+    /// Add a `Header` to the request.
+    fn header<K, V>(self, key: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>;
+
+    /// Add a set of Headers to the request.
+    fn headers(self, headers: HeaderMap) -> Self;
+
+    /// Enable HTTP basic authentication.
+    fn basic_auth<U, P>(self, username: U, password: Option<P>) -> Self
+    where
+        U: fmt::Display,
+        P: fmt::Display;
+
+    /// Enable HTTP bearer authentication.
+    fn bearer_auth<T>(self, token: T) -> Self
+    where
+        T: fmt::Display;
+
+    /// Set the request body.
+    fn body(self, body: Self::Body) -> Self;
+
+    /// The bytes of the body, or `None` when the body is a stream.
+    fn body_bytes(body: &Self::Body) -> Option<&[u8]>;
+
+    /// Enable a request timeout.
+    fn timeout(self, timeout: Duration) -> Self;
+
+    /// Send a multipart/form-data body, and returns the captured information
+    /// to be signed, e.g. the urlencoded text parts.
+    #[cfg(feature = "multipart")]
+    fn multipart(self, form: Self::MultipartForm) -> (Self, CapturedParts);
+
+    /// Attempt to clone the request builder.
+    fn try_clone(&self) -> Option<Self>;
+
+    /// Build the request, and returns its URL and headers.
+    fn build_parts(self) -> reqwest::Result<(Url, HeaderMap)>;
+}
+
+macro_rules! impl_inner_request_builder {
+    ($builder:ty, $client:ty, $body:ty, $multipart:ty) => {
+        impl private::Sealed for $builder {}
+
+        impl InnerRequestBuilder for $builder {
+            type Client = $client;
+            type Body = $body;
+            #[cfg(feature = "multipart")]
+            type MultipartForm = $multipart;
+
+            fn request<U: IntoUrl>(client: &$client, method: Method, url: U) -> Self {
+                client.request(method, url)
+            }
+
+            fn query<T: Serialize + ?Sized>(self, query: &T) -> Self {
+                <$builder>::query(self, query)
+            }
+
+            fn form<T: Serialize + ?Sized>(self, form: &T) -> Self {
+                <$builder>::form(self, form)
+            }
+
+            #[cfg(feature = "json")]
+            fn json<T: Serialize + ?Sized>(self, json: &T) -> Self {
+                <$builder>::json(self, json)
+            }
+
+            fn header<K, V>(self, key: K, value: V) -> Self
+            where
+                HeaderName: TryFrom<K>,
+                <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+                HeaderValue: TryFrom<V>,
+                <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+            {
+                <$builder>::header(self, key, value)
+            }
+
+            fn headers(self, headers: HeaderMap) -> Self {
+                <$builder>::headers(self, headers)
+            }
+
+            fn basic_auth<U, P>(self, username: U, password: Option<P>) -> Self
+            where
+                U: fmt::Display,
+                P: fmt::Display,
+            {
+                <$builder>::basic_auth(self, username, password)
+            }
+
+            fn bearer_auth<T>(self, token: T) -> Self
+            where
+                T: fmt::Display,
+            {
+                <$builder>::bearer_auth(self, token)
+            }
+
+            fn body(self, body: $body) -> Self {
+                <$builder>::body(self, body)
+            }
+
+            fn body_bytes(body: &$body) -> Option<&[u8]> {
+                body.as_bytes()
+            }
+
+            fn timeout(self, timeout: Duration) -> Self {
+                <$builder>::timeout(self, timeout)
+            }
+
+            #[cfg(feature = "multipart")]
+            fn multipart(self, form: $multipart) -> (Self, CapturedParts) {
+                let (form, parts) = form.into_parts();
+                (<$builder>::multipart(self, form), parts)
+            }
+
+            fn try_clone(&self) -> Option<Self> {
+                <$builder>::try_clone(self)
+            }
+
+            fn build_parts(self) -> reqwest::Result<(Url, HeaderMap)> {
+                let req = <$builder>::build(self)?;
+                Ok((req.url().clone(), req.headers().clone()))
+            }
+        }
+    };
+}
+
+impl_inner_request_builder!(
+    reqwest::RequestBuilder,
+    reqwest::Client,
+    reqwest::Body,
+    crate::MultipartForm
+);
+
+#[cfg(feature = "blocking")]
+impl_inner_request_builder!(
+    reqwest::blocking::RequestBuilder,
+    reqwest::blocking::Client,
+    reqwest::blocking::Body,
+    crate::blocking::MultipartForm
+);
+
+mod private {
+    pub trait Sealed {}
+}
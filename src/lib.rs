@@ -1,12 +1,25 @@
 /*!
-reqwest-oauth1: reqwest ♡ oauth1-request.
+reqwest-oauth1: reqwest ♡ OAuth1.
 
 Repository is here: https://github.com/karno/reqwest-oauth1
 
 # Overview
 
 This library provides OAuth 1.0a authorization capability to [reqwest](https://crates.io/crates/reqwest)
-crate by providing the thin (partial-)compatible interface layer built with [oauth1-request](https://crates.io/crates/oauth1-request) crate.
+crate by providing the thin (partial-)compatible interface layer.
+
+The signing follows [RFC 5849](https://tools.ietf.org/html/rfc5849).
+
+# Features
+
+- `reqwest` (default): the reqwest layer, e.g. `OAuthClientProvider` and `RequestBuilder`.
+- `std`: generates the nonce and the timestamp. Enabled by `reqwest`.
+- `multipart` (default), `json`: enables the corresponding reqwest feature.
+- `blocking`: the blocking API in the `blocking` module. It coexists with the asynchronous API.
+- `rsa-sha1`: the `RSA-SHA1` signature method.
+
+Without the default features, this crate is `no_std` (requires `alloc`),
+and you can sign the request with `Signer::sign`.
 
 # How to use
 
@@ -14,15 +27,7 @@ crate by providing the thin (partial-)compatible interface layer built with [oau
 
 ```rust
 use reqwest;
-
-#[cfg(feature = "blocking")]
-use reqwest::blocking::multipart;
-#[cfg(feature = "blocking")]
-use reqwest::blocking::Client;
-
-#[cfg(not(feature = "blocking"))]
 use reqwest::multipart;
-#[cfg(not(feature = "blocking"))]
 use reqwest::Client;
 
 use reqwest_oauth1::*;
@@ -59,10 +64,6 @@ use reqwest;
 use reqwest_oauth1::{OAuthClientProvider, TokenReaderFuture};
 
 async fn acquire_twitter_key() -> Result<(), reqwest_oauth1::Error> {
-    #[cfg(feature = "blocking")]
-    use reqwest::blocking::Client as Client;
-
-    #[cfg(not(feature = "blocking"))]
     use reqwest::Client;
 
     // prepare authorization info
@@ -78,7 +79,6 @@ async fn acquire_twitter_key() -> Result<(), reqwest_oauth1::Error> {
 
     let client = Client::new();
 
-    #[cfg(not(feature = "blocking"))]
     let resp = client
         .oauth1(secrets)
         .get(endpoint_reqtoken)
@@ -87,35 +87,15 @@ async fn acquire_twitter_key() -> Result<(), reqwest_oauth1::Error> {
         .parse_oauth_token()
         .await?;
 
-    #[cfg(feature = "blocking")]
-    let resp = client
-        .oauth1(secrets)
-        .get(endpoint_reqtoken)
-        .query(&[("oauth_callback", "oob")])
-        .send()
-        .parse_oauth_token()?;
     /*
     or
 
-    #[cfg(not(feature = "blocking"))]
     let resp = client
         .oauth1(secrets)
         .get(endpoint_reqtoken)
         .query(&[("oauth_callback", "oob")])
         .send()
         .await?;
-
-    #[cfg(feature = "blocking")]
-    let resp = client
-        .oauth1(secrets)
-        .get(endpoint_reqtoken)
-        .query(&[("oauth_callback", "oob")])
-        .send()?;
-
-    #[cfg(not(feature = "blocking"))]
-    let resp = serde_urlencoded::from_str::<reqwest_oauth1::TokenResponse>(resp.text().as_str()).unwrap();
-
-    #[cfg(feature = "blocking")]
     let resp = serde_urlencoded::from_str::<reqwest_oauth1::TokenResponse>(resp.text().await?.as_str()).unwrap();
     */
 
@@ -136,7 +116,6 @@ async fn acquire_twitter_key() -> Result<(), reqwest_oauth1::Error> {
 
     let client = Client::new();
 
-    #[cfg(not(feature = "blocking"))]
     let resp = client
         .oauth1(secrets)
         .get(endpoint_acctoken)
@@ -145,14 +124,6 @@ async fn acquire_twitter_key() -> Result<(), reqwest_oauth1::Error> {
         .parse_oauth_token()
         .await?;
 
-    #[cfg(feature = "blocking")]
-    let resp = client
-        .oauth1(secrets)
-        .get(endpoint_acctoken)
-        .query(&[("oauth_verifier", pin)])
-        .send()
-        .parse_oauth_token()?;
-
     println!(
         "your token and secret is: \n token: {}\n secret: {}",
         resp.oauth_token, resp.oauth_token_secret
@@ -161,36 +132,104 @@ async fn acquire_twitter_key() -> Result<(), reqwest_oauth1::Error> {
     Ok(())
 }
 ```
+
+## Blocking API
+
+With the `blocking` feature, `reqwest::blocking::Client` is also available
+for `oauth1` in the same build. See the `blocking` module.
 */
-mod client;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod body_hash;
+mod clock;
 mod error;
-mod request;
+mod hmac_sha1;
+mod hmac_sha2;
+mod nonce;
+mod plaintext;
+mod report;
 mod secrets;
+mod signature_method;
 mod signer;
+mod util;
 
-#[cfg(not(feature = "blocking"))]
+#[cfg(feature = "reqwest")]
+mod backend;
+#[cfg(feature = "reqwest")]
+mod client;
+#[cfg(feature = "reqwest")]
+mod flow;
+#[cfg(feature = "reqwest")]
+mod inner;
+#[cfg(feature = "reqwest")]
+mod problem;
+#[cfg(feature = "reqwest")]
+mod request;
+#[cfg(feature = "reqwest")]
+mod skew;
+
+#[cfg(feature = "multipart")]
+mod multipart;
+
+#[cfg(feature = "rsa-sha1")]
+mod rsa_sha1;
+
+#[cfg(feature = "reqwest")]
 mod token_reader;
 
+#[cfg(all(test, feature = "reqwest"))]
+mod test_server;
+
 #[cfg(feature = "blocking")]
-mod token_reader_blocking;
+pub mod blocking;
 
 //#[cfg(test)]
 //mod usage_test;
 
 // exposed to external program
-pub use client::{Client, DefaultSM, OAuthClientProvider};
-pub use error::{Error, Result, SignResult, SignerError, TokenReaderError, TokenReaderResult};
-pub use request::RequestBuilder;
+pub use clock::{Clock, FixedClock};
+pub use error::{SignResult, SignerError};
+pub use hmac_sha1::{HmacSha1, HmacSha1 as DefaultSM, HmacSha1Sign, HmacSha1Signature};
+pub use hmac_sha2::{HmacSha256, HmacSha2Sign, HmacSha2Signature, HmacSha512};
+pub use nonce::{NonceGenerator, SequenceNonce};
+pub use plaintext::{Plaintext, PlaintextSign, PlaintextSignature};
+pub use report::SigningReport;
 pub use secrets::{Secrets, SecretsProvider};
-pub use signer::{OAuthParameters, Signer};
+pub use signature_method::{Sign, SignatureMethod};
+pub use signer::{OAuthParameters, ParameterTransmission, SignedParameters, Signer};
+
+#[cfg(feature = "std")]
+pub use clock::SystemClock;
+#[cfg(feature = "std")]
+pub use nonce::RandomNonce;
+
+#[cfg(feature = "reqwest")]
+pub use backend::{BackendError, ExternalSigner, LocalSigningBackend, SigningBackend};
+#[cfg(feature = "reqwest")]
+pub use client::{Client, OAuthBackendProvider, OAuthClientProvider};
+#[cfg(feature = "reqwest")]
+pub use error::{Error, Result, TokenReaderError, TokenReaderResult};
+#[cfg(feature = "reqwest")]
+pub use flow::{AccessToken, OAuth1Flow, RequestToken};
+#[cfg(feature = "reqwest")]
+pub use inner::InnerRequestBuilder;
+#[cfg(feature = "reqwest")]
+pub use problem::{OAuthProblem, ProblemReport};
+#[cfg(feature = "reqwest")]
+pub use request::RequestBuilder;
+#[cfg(feature = "reqwest")]
+pub use skew::ClockSkew;
 
-#[cfg(not(feature = "blocking"))]
-pub use token_reader::{TokenReader, TokenReaderFuture, TokenResponse};
+#[cfg(feature = "multipart")]
+pub use multipart::MultipartForm;
 
-#[cfg(feature = "blocking")]
-pub use token_reader_blocking::{
-    TokenReader, TokenReaderBlocking as TokenReaderFuture, TokenResponse,
-};
+#[cfg(feature = "rsa-sha1")]
+pub use rsa_sha1::{RsaSha1, RsaSha1Sign, RsaSha1Signature};
+
+#[cfg(feature = "reqwest")]
+pub use token_reader::{ErrorResponse, TokenReader, TokenReaderFuture, TokenResponse};
 
 // exposed constant variables
 /// Represents `oauth_callback`.
@@ -211,3 +250,6 @@ pub(crate) const OAUTH_KEY_PREFIX: &str = "oauth_";
 pub(crate) const OAUTH_SIGNATURE_METHOD_KEY: &str = "oauth_signature_method";
 pub(crate) const OAUTH_CONSUMER_KEY: &str = "oauth_consumer_key";
 pub(crate) const OAUTH_TOKEN_KEY: &str = "oauth_token";
+pub(crate) const OAUTH_SIGNATURE_KEY: &str = "oauth_signature";
+pub(crate) const OAUTH_BODY_HASH_KEY: &str = "oauth_body_hash";
+pub(crate) const OAUTH_VERSION_VALUE: &str = "1.0";
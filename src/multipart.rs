@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+
+/// The captured information of the multipart/form-data body to be signed.
+#[derive(Debug, Default)]
+pub struct CapturedParts {
+    /// the urlencoded text parts to be signed, or `None` when they are not signed
+    pub(crate) signed_text_parts: Option<String>,
+    /// the `oauth_*` text parts, which are signed as the OAuth parameters
+    pub(crate) oauth_parameters: HashMap<String, String>,
+    /// whether the form has the parts which are never signed, e.g. files
+    pub(crate) has_unsigned_parts: bool,
+}
+
+macro_rules! impl_multipart_form {
+    ($(#[$meta:meta])* $form:ty, $part:ty) => {
+        $(#[$meta])*
+        #[derive(Debug)]
+        pub struct MultipartForm {
+            inner: $form,
+            text_parts: Vec<(String, String)>,
+            oauth_parameters: std::collections::HashMap<String, String>,
+            sign_text_parts: bool,
+            has_unsigned_parts: bool,
+        }
+
+        impl Default for MultipartForm {
+            fn default() -> Self {
+                MultipartForm::new()
+            }
+        }
+
+        impl From<$form> for MultipartForm {
+            /// Wrap the reqwest's `Form`. Its parts are not signed.
+            fn from(form: $form) -> Self {
+                MultipartForm {
+                    has_unsigned_parts: true,
+                    ..MultipartForm::new_with(form)
+                }
+            }
+        }
+
+        impl MultipartForm {
+            /// Creates a new Form without any content.
+            pub fn new() -> Self {
+                MultipartForm::new_with(<$form>::new())
+            }
+
+            fn new_with(form: $form) -> Self {
+                MultipartForm {
+                    inner: form,
+                    text_parts: Vec::new(),
+                    oauth_parameters: std::collections::HashMap::new(),
+                    sign_text_parts: false,
+                    has_unsigned_parts: false,
+                }
+            }
+
+            /// Get the boundary that this form will use.
+            pub fn boundary(&self) -> &str {
+                self.inner.boundary()
+            }
+
+            /// Add a data field with supplied name and value.
+            ///
+            /// # Note
+            /// The `oauth_*` (and `realm`) fields are not sent as the parts,
+            /// they are captured as the OAuth parameters as same as the form.
+            pub fn text<T, U>(mut self, name: T, value: U) -> Self
+            where
+                T: Into<std::borrow::Cow<'static, str>>,
+                U: Into<std::borrow::Cow<'static, str>>,
+            {
+                let name = name.into();
+                let value = value.into();
+                if name.starts_with($crate::OAUTH_KEY_PREFIX) || name == $crate::REALM_KEY {
+                    self.oauth_parameters
+                        .insert(name.into_owned(), value.into_owned());
+                    return self;
+                }
+                self.text_parts.push((name.to_string(), value.to_string()));
+                MultipartForm {
+                    inner: self.inner.text(name, value),
+                    ..self
+                }
+            }
+
+            /// Adds a customized Part.
+            ///
+            /// # Note
+            /// The part is not signed even if it is a text,
+            /// so the signing fails in the strict mode.
+            pub fn part<T>(self, name: T, part: $part) -> Self
+            where
+                T: Into<std::borrow::Cow<'static, str>>,
+            {
+                MultipartForm {
+                    inner: self.inner.part(name, part),
+                    has_unsigned_parts: true,
+                    ..self
+                }
+            }
+
+            /// Include the text parts in the signature (default: `false`).
+            pub fn sign_text_parts<T>(self, sign: T) -> Self
+            where
+                T: Into<bool>,
+            {
+                MultipartForm {
+                    sign_text_parts: sign.into(),
+                    ..self
+                }
+            }
+
+            /// Destructure into the reqwest's `Form` and the information to be signed.
+            pub(crate) fn into_parts(self) -> ($form, $crate::multipart::CapturedParts) {
+                let signed_text_parts = if self.sign_text_parts {
+                    Some(
+                        url::form_urlencoded::Serializer::new(String::new())
+                            .extend_pairs(self.text_parts)
+                            .finish(),
+                    )
+                } else {
+                    None
+                };
+                let parts = $crate::multipart::CapturedParts {
+                    signed_text_parts,
+                    oauth_parameters: self.oauth_parameters,
+                    has_unsigned_parts: self.has_unsigned_parts,
+                };
+                (self.inner, parts)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_multipart_form;
+
+impl_multipart_form!(
+    /**
+    A multipart/form-data body, which records its text parts for the OAuth signing.
+
+    The text parts are not included in the signature by default.
+    Some providers expect them to be signed, then you can call `sign_text_parts`.
+    File parts (or any other parts added with `part`) are never signed.
+
+    # Basic usage
+
+    ```rust
+    use reqwest_oauth1::*;
+    use reqwest::Client;
+
+    let consumer_key = "[CONSUMER_KEY]";
+    let consumer_secret = "[CONSUMER_SECRET]";
+    let secrets = reqwest_oauth1::Secrets::new(consumer_key, consumer_secret);
+
+    let form = MultipartForm::new()
+        .text("title", "vacation")
+        .sign_text_parts(true);
+
+    let req = Client::new()
+        .oauth1(secrets)
+        .post("https://example.com/upload")
+        .multipart(form)
+        // and so on...
+        ;
+    ```
+
+    */
+    reqwest::multipart::Form,
+    reqwest::multipart::Part
+);
@@ -0,0 +1,94 @@
+use alloc::{format, string::String};
+use core::{
+    fmt::Debug,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+#[cfg(feature = "std")]
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+#[cfg(feature = "std")]
+use rand::RngCore;
+
+/**
+Source of the `oauth_nonce` value, called for each request.
+
+The random nonce is used by default (with the `std` feature).
+You can replace it with `OAuthParameters::nonce_generator`, e.g. to make your tests reproducible.
+
+# Basic usage
+
+```rust
+use reqwest_oauth1::*;
+
+let params = OAuthParameters::new().nonce_generator(SequenceNonce::new("test-nonce-"));
+```
+
+*/
+pub trait NonceGenerator: Debug + Send + Sync {
+    /// Generate a nonce, which must be unique for each request.
+    fn generate(&self) -> String;
+}
+
+/// The `NonceGenerator` which generates 32 characters of the url-safe base64
+/// from the cryptographically secure random number generator.
+///
+/// This struct is available only when the `std` feature is enabled.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomNonce;
+
+#[cfg(feature = "std")]
+impl NonceGenerator for RandomNonce {
+    fn generate(&self) -> String {
+        let mut bytes = [0u8; 24];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+}
+
+/// The `NonceGenerator` which generates the prefix followed by a sequence
+/// number (`prefix0`, `prefix1`, ...), for testing.
+#[derive(Debug, Default)]
+pub struct SequenceNonce {
+    prefix: String,
+    next: AtomicUsize,
+}
+
+impl SequenceNonce {
+    pub fn new<T>(prefix: T) -> Self
+    where
+        T: Into<String>,
+    {
+        SequenceNonce {
+            prefix: prefix.into(),
+            next: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl NonceGenerator for SequenceNonce {
+    fn generate(&self) -> String {
+        let n = self.next.fetch_add(1, Ordering::Relaxed);
+        format!("{}{}", self.prefix, n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequence_nonce() {
+        let generator = SequenceNonce::new("nonce-");
+        assert_eq!(generator.generate(), "nonce-0");
+        assert_eq!(generator.generate(), "nonce-1");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn random_nonce_is_unique() {
+        let nonce = RandomNonce.generate();
+        assert_eq!(nonce.len(), 32);
+        assert_ne!(nonce, RandomNonce.generate());
+    }
+}
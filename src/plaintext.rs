@@ -0,0 +1,126 @@
+use alloc::{format, string::String};
+use core::fmt::{self, Display};
+
+use crate::util::percent_encode;
+use crate::{Sign, SignatureMethod};
+
+/// Represents the `oauth_signature_method` value of the PLAINTEXT method.
+pub(crate) const PLAINTEXT_METHOD_NAME: &str = "PLAINTEXT";
+
+/**
+The `PLAINTEXT` signature method.
+
+cf. https://tools.ietf.org/html/rfc5849#section-3.4.4
+
+This method sends the consumer secret and the token secret as the signature,
+so the request must be sent over a secure channel.
+The signer refuses to sign any request whose URL is not `https`
+unless you call `OAuthParameters::allow_insecure_plaintext`.
+
+# Basic usage
+
+```rust
+use reqwest_oauth1::*;
+
+use reqwest::Client;
+
+let consumer_key = "[CONSUMER_KEY]";
+let consumer_secret = "[CONSUMER_SECRET]";
+let secrets = reqwest_oauth1::Secrets::new(consumer_key, consumer_secret);
+
+let params = reqwest_oauth1::OAuthParameters::new()
+    .signature_method(Plaintext);
+
+let req = Client::new()
+    .oauth1_with_params(secrets, params)
+    .get("https://example.com/")
+    // and so on...
+    ;
+```
+
+*/
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Plaintext;
+
+/// A type that just returns the signing key as the signature.
+#[derive(Debug, Clone)]
+pub struct PlaintextSign(String);
+
+/// A signature produced by a `PlaintextSign`.
+#[derive(Debug, Clone)]
+pub struct PlaintextSignature(String);
+
+impl SignatureMethod for Plaintext {
+    type Sign = PlaintextSign;
+
+    fn sign_with(
+        self,
+        consumer_secret: impl Display,
+        token_secret: Option<impl Display>,
+    ) -> PlaintextSign {
+        // secrets are already percent-encoded by the signer
+        match token_secret {
+            Some(ts) => PlaintextSign(format!("{}&{}", consumer_secret, ts)),
+            None => PlaintextSign(format!("{}&", consumer_secret)),
+        }
+    }
+}
+
+impl Sign for PlaintextSign {
+    type Signature = PlaintextSignature;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        PLAINTEXT_METHOD_NAME
+    }
+
+    fn request_method(&mut self, _method: &str) {}
+
+    fn uri(&mut self, _uri: impl Display) {}
+
+    fn parameter(&mut self, _key: &str, _value: impl Display) {}
+
+    fn delimiter(&mut self) {}
+
+    fn finish(self) -> PlaintextSignature {
+        PlaintextSignature(self.0)
+    }
+}
+
+impl Display for PlaintextSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the signature itself must be encoded again to be put in the header
+        Display::fmt(&percent_encode(&self.0), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn signature_with_token_secret() {
+        // https://tools.ietf.org/html/rfc5849#section-3.4.4
+        let sign = Plaintext.sign_with("kd94hf93k423kf44", Some("pfkkdhi9sl3r4s00"));
+        assert_eq!(sign.get_signature_method_name(), "PLAINTEXT");
+        assert_eq!(
+            sign.finish().to_string(),
+            "kd94hf93k423kf44%26pfkkdhi9sl3r4s00"
+        );
+    }
+
+    #[test]
+    fn signature_without_token_secret() {
+        // https://tools.ietf.org/html/rfc5849#section-1.2
+        let sign = Plaintext.sign_with("ja893SD9", None::<&str>);
+        assert_eq!(sign.finish().to_string(), "ja893SD9%26");
+    }
+
+    #[test]
+    fn signature_is_encoded_twice() {
+        // the signer passes the secrets after percent-encoding them
+        let sign = Plaintext.sign_with("a%20b", Some("c%2Bd"));
+        assert_eq!(sign.finish().to_string(), "a%2520b%26c%252Bd");
+    }
+}
@@ -0,0 +1,429 @@
+use std::fmt::{self, Display};
+
+use http::{header::WWW_AUTHENTICATE, HeaderMap};
+use percent_encoding::percent_decode_str;
+
+use crate::ErrorResponse;
+
+const OAUTH_PROBLEM_KEY: &str = "oauth_problem";
+const OAUTH_PROBLEM_ADVICE_KEY: &str = "oauth_problem_advice";
+const OAUTH_ACCEPTABLE_VERSIONS_KEY: &str = "oauth_acceptable_versions";
+const OAUTH_PARAMETERS_ABSENT_KEY: &str = "oauth_parameters_absent";
+const OAUTH_PARAMETERS_REJECTED_KEY: &str = "oauth_parameters_rejected";
+const OAUTH_ACCEPTABLE_TIMESTAMPS_KEY: &str = "oauth_acceptable_timestamps";
+
+/**
+Represents the `oauth_problem` reported by the provider, with its advice.
+
+cf. [OAuth Problem Reporting](https://wiki.oauth.net/w/page/12238543/ProblemReporting)
+
+# Basic usage
+
+```rust
+use reqwest_oauth1::OAuthProblem;
+
+let problem = OAuthProblem::from_parameters([
+    ("oauth_problem", "timestamp_refused"),
+    ("oauth_acceptable_timestamps", "137131200-137131800"),
+]);
+assert_eq!(
+    problem,
+    Some(OAuthProblem::TimestampRefused {
+        acceptable_timestamps: Some((137_131_200, 137_131_800)),
+    })
+);
+```
+
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OAuthProblem {
+    /// `version_rejected`: the `oauth_version` is not supported.
+    VersionRejected {
+        /// `oauth_acceptable_versions`: the range of the supported versions.
+        acceptable_versions: Option<(String, String)>,
+    },
+    /// `parameter_absent`: the required parameters are not sent.
+    ParameterAbsent {
+        /// `oauth_parameters_absent`: the names of the missing parameters.
+        parameters_absent: Vec<String>,
+    },
+    /// `parameter_rejected`: the unexpected parameters are sent.
+    ParameterRejected {
+        /// `oauth_parameters_rejected`: the names of the rejected parameters.
+        parameters_rejected: Vec<String>,
+    },
+    /// `timestamp_refused`: the `oauth_timestamp` is too old or too new.
+    TimestampRefused {
+        /// `oauth_acceptable_timestamps`: the range of the acceptable timestamps.
+        acceptable_timestamps: Option<(u64, u64)>,
+    },
+    /// `nonce_used`: the `oauth_nonce` has already been used.
+    NonceUsed,
+    /// `signature_method_rejected`: the `oauth_signature_method` is not supported.
+    SignatureMethodRejected,
+    /// `signature_invalid`: the `oauth_signature` is invalid.
+    SignatureInvalid,
+    /// `consumer_key_unknown`: the `oauth_consumer_key` is unknown.
+    ConsumerKeyUnknown,
+    /// `consumer_key_rejected`: the `oauth_consumer_key` is permanently unacceptable.
+    ConsumerKeyRejected,
+    /// `consumer_key_refused`: the `oauth_consumer_key` is temporarily unacceptable.
+    ConsumerKeyRefused,
+    /// `token_used`: the `oauth_token` has already been used.
+    TokenUsed,
+    /// `token_expired`: the `oauth_token` has expired.
+    TokenExpired,
+    /// `token_revoked`: the `oauth_token` has been revoked.
+    TokenRevoked,
+    /// `token_rejected`: the `oauth_token` is not acceptable.
+    TokenRejected,
+    /// `additional_authorization_required`: the token lacks some authorization.
+    AdditionalAuthorizationRequired,
+    /// `permission_unknown`: the user has not decided the authorization yet.
+    PermissionUnknown,
+    /// `permission_denied`: the user denied the authorization.
+    PermissionDenied,
+    /// `user_refused`: the user refused the authorization.
+    UserRefused,
+    /// The problem not defined in the extension.
+    Other(String),
+}
+
+impl OAuthProblem {
+    /// Extract the problem from the parameters of the response,
+    /// or returns `None` when `oauth_problem` is not found.
+    pub fn from_parameters<'a, I>(parameters: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let parameters = parameters.into_iter().collect::<Vec<_>>();
+        let find = |key: &str| parameters.iter().find(|(k, _)| *k == key).map(|(_, v)| *v);
+        let names = |key: &str| {
+            find(key)
+                .map(|v| v.split('&').map(str::to_string).collect())
+                .unwrap_or_default()
+        };
+        let problem = match find(OAUTH_PROBLEM_KEY)? {
+            "version_rejected" => OAuthProblem::VersionRejected {
+                acceptable_versions: find(OAUTH_ACCEPTABLE_VERSIONS_KEY)
+                    .and_then(|v| v.split_once('-'))
+                    .map(|(min, max)| (min.trim().to_string(), max.trim().to_string())),
+            },
+            "parameter_absent" => OAuthProblem::ParameterAbsent {
+                parameters_absent: names(OAUTH_PARAMETERS_ABSENT_KEY),
+            },
+            "parameter_rejected" => OAuthProblem::ParameterRejected {
+                parameters_rejected: names(OAUTH_PARAMETERS_REJECTED_KEY),
+            },
+            "timestamp_refused" => OAuthProblem::TimestampRefused {
+                acceptable_timestamps: find(OAUTH_ACCEPTABLE_TIMESTAMPS_KEY)
+                    .and_then(parse_acceptable_timestamps),
+            },
+            "nonce_used" => OAuthProblem::NonceUsed,
+            "signature_method_rejected" => OAuthProblem::SignatureMethodRejected,
+            "signature_invalid" => OAuthProblem::SignatureInvalid,
+            "consumer_key_unknown" => OAuthProblem::ConsumerKeyUnknown,
+            "consumer_key_rejected" => OAuthProblem::ConsumerKeyRejected,
+            "consumer_key_refused" => OAuthProblem::ConsumerKeyRefused,
+            "token_used" => OAuthProblem::TokenUsed,
+            "token_expired" => OAuthProblem::TokenExpired,
+            "token_revoked" => OAuthProblem::TokenRevoked,
+            "token_rejected" => OAuthProblem::TokenRejected,
+            "additional_authorization_required" => OAuthProblem::AdditionalAuthorizationRequired,
+            "permission_unknown" => OAuthProblem::PermissionUnknown,
+            "permission_denied" => OAuthProblem::PermissionDenied,
+            "user_refused" => OAuthProblem::UserRefused,
+            other => OAuthProblem::Other(other.to_string()),
+        };
+        Some(problem)
+    }
+
+    /// Extract the problem from the `WWW-Authenticate: OAuth ...` header.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let parameters = parse_oauth_challenge(headers);
+        OAuthProblem::from_parameters(parameters.iter().map(|(k, v)| (k.as_str(), v.as_str())))
+    }
+
+    /// The value of `oauth_problem`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            OAuthProblem::VersionRejected { .. } => "version_rejected",
+            OAuthProblem::ParameterAbsent { .. } => "parameter_absent",
+            OAuthProblem::ParameterRejected { .. } => "parameter_rejected",
+            OAuthProblem::TimestampRefused { .. } => "timestamp_refused",
+            OAuthProblem::NonceUsed => "nonce_used",
+            OAuthProblem::SignatureMethodRejected => "signature_method_rejected",
+            OAuthProblem::SignatureInvalid => "signature_invalid",
+            OAuthProblem::ConsumerKeyUnknown => "consumer_key_unknown",
+            OAuthProblem::ConsumerKeyRejected => "consumer_key_rejected",
+            OAuthProblem::ConsumerKeyRefused => "consumer_key_refused",
+            OAuthProblem::TokenUsed => "token_used",
+            OAuthProblem::TokenExpired => "token_expired",
+            OAuthProblem::TokenRevoked => "token_revoked",
+            OAuthProblem::TokenRejected => "token_rejected",
+            OAuthProblem::AdditionalAuthorizationRequired => "additional_authorization_required",
+            OAuthProblem::PermissionUnknown => "permission_unknown",
+            OAuthProblem::PermissionDenied => "permission_denied",
+            OAuthProblem::UserRefused => "user_refused",
+            OAuthProblem::Other(v) => v,
+        }
+    }
+}
+
+impl Display for OAuthProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Represents the error response which reports the `oauth_problem`.
+#[derive(Debug, Clone)]
+pub struct ProblemReport {
+    /// The reported problem
+    pub problem: OAuthProblem,
+    /// `oauth_problem_advice`: the human readable description
+    pub advice: Option<String>,
+    /// The error response
+    pub response: ErrorResponse,
+}
+
+impl ProblemReport {
+    /// Read the problem from the response of your API calls, e.g. `send()`.
+    ///
+    /// Returns the response as `ErrorResponse`, when no problem is reported.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the body could not be read.
+    pub async fn from_response(
+        response: reqwest::Response,
+    ) -> reqwest::Result<Result<Self, ErrorResponse>> {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.text().await?;
+        Ok(ProblemReport::extract(ErrorResponse::new(
+            status, headers, body,
+        )))
+    }
+
+    /// Read the problem from the response of the blocking API calls.
+    ///
+    /// Returns the response as `ErrorResponse`, when no problem is reported.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the body could not be read.
+    #[cfg(feature = "blocking")]
+    pub fn from_blocking_response(
+        response: reqwest::blocking::Response,
+    ) -> reqwest::Result<Result<Self, ErrorResponse>> {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.text()?;
+        Ok(ProblemReport::extract(ErrorResponse::new(
+            status, headers, body,
+        )))
+    }
+
+    /// Extract the problem from the body, or the `WWW-Authenticate` header of
+    /// the response. Returns the response as is, when no problem is reported.
+    pub(crate) fn extract(response: ErrorResponse) -> Result<Self, ErrorResponse> {
+        let from_body = OAuthProblem::from_parameters(
+            response
+                .parameters
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str())),
+        );
+        let (problem, advice) = match from_body {
+            Some(problem) => (
+                problem,
+                response.parameters.get(OAUTH_PROBLEM_ADVICE_KEY).cloned(),
+            ),
+            None => {
+                let challenge = parse_oauth_challenge(&response.headers);
+                let problem = OAuthProblem::from_parameters(
+                    challenge.iter().map(|(k, v)| (k.as_str(), v.as_str())),
+                );
+                let Some(problem) = problem else {
+                    return Err(response);
+                };
+                let advice = challenge
+                    .into_iter()
+                    .find(|(k, _)| k == OAUTH_PROBLEM_ADVICE_KEY)
+                    .map(|(_, v)| v);
+                (problem, advice)
+            }
+        };
+        Ok(ProblemReport {
+            problem,
+            advice,
+            response,
+        })
+    }
+}
+
+/// Parse the parameters of the `WWW-Authenticate: OAuth ...` header.
+fn parse_oauth_challenge(headers: &HeaderMap) -> Vec<(String, String)> {
+    let Some(challenge) = headers
+        .get(WWW_AUTHENTICATE)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return Vec::new();
+    };
+    let challenge = challenge.trim_start();
+    let parameters = match challenge.get(..6) {
+        Some(scheme) if scheme.eq_ignore_ascii_case("OAuth ") => &challenge[6..],
+        _ => return Vec::new(),
+    };
+    parameters
+        .split(',')
+        .filter_map(|item| item.split_once('='))
+        .map(|(k, v)| {
+            let v = percent_decode_str(v.trim().trim_matches('"'));
+            (k.trim().to_string(), v.decode_utf8_lossy().into_owned())
+        })
+        .collect()
+}
+
+/// Parse `oauth_acceptable_timestamps` (`min-max`).
+///
+/// The inverted range (`min > max`) is ignored.
+fn parse_acceptable_timestamps(value: &str) -> Option<(u64, u64)> {
+    let (min, max) = value.split_once('-')?;
+    let min = min.trim().parse::<u64>().ok()?;
+    let max = max.trim().parse::<u64>().ok()?;
+    (min <= max).then_some((min, max))
+}
+
+#[cfg(test)]
+mod tests {
+    use http::{HeaderValue, StatusCode};
+
+    use super::*;
+
+    #[test]
+    fn parse_problem_advice() {
+        let problem = OAuthProblem::from_parameters([
+            ("oauth_problem", "parameter_absent"),
+            ("oauth_parameters_absent", "oauth_nonce&oauth_timestamp"),
+        ]);
+        assert_eq!(
+            problem,
+            Some(OAuthProblem::ParameterAbsent {
+                parameters_absent: vec!["oauth_nonce".into(), "oauth_timestamp".into()],
+            })
+        );
+
+        let problem = OAuthProblem::from_parameters([
+            ("oauth_problem", "version_rejected"),
+            ("oauth_acceptable_versions", "1.0-1.0"),
+        ]);
+        assert_eq!(
+            problem,
+            Some(OAuthProblem::VersionRejected {
+                acceptable_versions: Some(("1.0".into(), "1.0".into())),
+            })
+        );
+
+        let problem = OAuthProblem::from_parameters([
+            ("oauth_problem", "timestamp_refused"),
+            ("oauth_acceptable_timestamps", "2100-1900"),
+        ]);
+        assert_eq!(
+            problem,
+            Some(OAuthProblem::TimestampRefused {
+                acceptable_timestamps: None,
+            })
+        );
+
+        let problem = OAuthProblem::from_parameters([("oauth_problem", "nonce_used")]).unwrap();
+        assert_eq!(problem, OAuthProblem::NonceUsed);
+        assert_eq!(problem.to_string(), "nonce_used");
+
+        let problem = OAuthProblem::from_parameters([("oauth_problem", "rate_limited")]).unwrap();
+        assert_eq!(problem, OAuthProblem::Other("rate_limited".into()));
+        assert_eq!(problem.to_string(), "rate_limited");
+
+        assert_eq!(OAuthProblem::from_parameters([("foo", "bar")]), None);
+    }
+
+    #[test]
+    fn extract_problem_from_body() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            WWW_AUTHENTICATE,
+            HeaderValue::from_static("OAuth oauth_problem=\"signature_invalid\""),
+        );
+        let response = ErrorResponse::new(
+            StatusCode::UNAUTHORIZED,
+            headers,
+            "oauth_problem=token_expired&oauth_problem_advice=try%20again".to_string(),
+        );
+        let report = ProblemReport::extract(response).unwrap();
+        assert_eq!(report.problem, OAuthProblem::TokenExpired);
+        assert_eq!(report.advice.as_deref(), Some("try again"));
+        assert_eq!(report.response.status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn extract_problem_from_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            WWW_AUTHENTICATE,
+            HeaderValue::from_static(
+                "OAuth realm=\"Photos\", oauth_problem=\"timestamp_refused\", \
+                 oauth_acceptable_timestamps=\"1900-2100\", \
+                 oauth_problem_advice=\"check%20your%20clock\"",
+            ),
+        );
+        let response = ErrorResponse::new(StatusCode::UNAUTHORIZED, headers, String::new());
+        let report = ProblemReport::extract(response).unwrap();
+        assert_eq!(
+            report.problem,
+            OAuthProblem::TimestampRefused {
+                acceptable_timestamps: Some((1900, 2100)),
+            }
+        );
+        assert_eq!(report.advice.as_deref(), Some("check your clock"));
+    }
+
+    #[tokio::test]
+    async fn read_problem_from_response() {
+        let response = http::Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .header(
+                WWW_AUTHENTICATE,
+                "OAuth realm=\"Photos\", oauth_problem=\"nonce_used\"",
+            )
+            .body(String::new())
+            .unwrap();
+        let report = ProblemReport::from_response(response.into())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(report.problem, OAuthProblem::NonceUsed);
+
+        let response = http::Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body("not found".to_string())
+            .unwrap();
+        let response = ProblemReport::from_response(response.into())
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(response.status, StatusCode::NOT_FOUND);
+        assert_eq!(response.body, "not found");
+    }
+
+    #[test]
+    fn keep_response_without_problem() {
+        let response = ErrorResponse::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            HeaderMap::new(),
+            "error=internal".to_string(),
+        );
+        let response = ProblemReport::extract(response).unwrap_err();
+        assert_eq!(response.body, "error=internal");
+    }
+}
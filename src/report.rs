@@ -0,0 +1,104 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Display};
+
+use percent_encoding::percent_decode_str;
+
+/**
+The details of the OAuth signing, to debug the signature rejected by the server.
+
+You can compare them with the documents provided by the server.
+
+# Basic usage
+
+```rust
+use reqwest_oauth1::*;
+
+use reqwest::Client;
+
+let secrets = reqwest_oauth1::Secrets::new("[CONSUMER_KEY]", "[CONSUMER_SECRET]");
+let params = reqwest_oauth1::OAuthParameters::new()
+    .nonce("[NONCE]")
+    .timestamp(100_000_001u64);
+
+let report = Client::new()
+    .oauth1_with_params(secrets, params)
+    .get("https://example.com/?a=b")
+    .signing_report()
+    .unwrap();
+println!("{}", report);
+```
+
+*/
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SigningReport {
+    /// HTTP request method in uppercase.
+    pub method: String,
+    /// base string URI, without the query and the fragment.
+    ///
+    /// cf. https://tools.ietf.org/html/rfc5849#section-3.4.1.2
+    pub normalized_url: String,
+    /// percent-encoded parameters in the sorted order, including `oauth_*` parameters.
+    ///
+    /// cf. https://tools.ietf.org/html/rfc5849#section-3.4.1.3.2
+    pub parameters: Vec<(String, String)>,
+    /// signature base string.
+    pub base_string: String,
+    /// signing key in the form of `consumer_secret&token_secret`, with the
+    /// secrets redacted.
+    ///
+    /// Note that RSA-SHA1 signs with the private key instead.
+    pub signing_key: String,
+    /// value of the Authorization header.
+    ///
+    /// The same parameters are sent in the query or the form body instead,
+    /// when the other `ParameterTransmission` is specified.
+    pub authorization_header: String,
+}
+
+impl SigningReport {
+    /// Destructure the signature base string.
+    pub(crate) fn new(
+        base_string: String,
+        signing_key: String,
+        authorization_header: String,
+    ) -> Self {
+        let mut items = base_string.splitn(3, '&');
+        let method = items.next().unwrap_or_default().to_string();
+        let normalized_url = decode(items.next().unwrap_or_default());
+        // parameters are encoded twice in the base string
+        let parameters = decode(items.next().unwrap_or_default())
+            .split('&')
+            .filter_map(|item| item.split_once('='))
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        SigningReport {
+            method,
+            normalized_url,
+            parameters,
+            base_string,
+            signing_key,
+            authorization_header,
+        }
+    }
+}
+
+fn decode(input: &str) -> String {
+    percent_decode_str(input).decode_utf8_lossy().into_owned()
+}
+
+impl Display for SigningReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "method: {}", self.method)?;
+        writeln!(f, "normalized url: {}", self.normalized_url)?;
+        writeln!(f, "parameters:")?;
+        for (key, value) in self.parameters.iter() {
+            writeln!(f, "  {}={}", key, value)?;
+        }
+        writeln!(f, "base string: {}", self.base_string)?;
+        writeln!(f, "signing key: {}", self.signing_key)?;
+        write!(f, "authorization header: {}", self.authorization_header)
+    }
+}
@@ -3,57 +3,114 @@
 // for further information(including license information),
 // please visit their repository: https://github.com/seanmonstar/reqwest .
 // ----------------------------------------------------------------------------
-use std::{collections::HashMap, convert::TryFrom, fmt, time::Duration};
-
-use http::{header::AUTHORIZATION, Method};
-use oauth1_request::signature_method::HmacSha1 as DefaultSM;
-use oauth1_request::signature_method::SignatureMethod;
-use reqwest::{
-    header::HeaderMap, header::HeaderName, header::HeaderValue,
-    IntoUrl,
-};
-
-#[cfg(feature = "blocking")]
-use reqwest::blocking::{
-    RequestBuilder as ReqwestRequestBuilder, Response,
-    Client as ReqwestClient, Body
-};
+use std::{borrow::Cow, collections::HashMap, convert::TryFrom, fmt, time::Duration};
 
-#[cfg(not(feature = "blocking"))]
-use reqwest::{
-    RequestBuilder as ReqwestRequestBuilder, Response,
-    Client as ReqwestClient, Body
+use http::{
+    header::{AUTHORIZATION, CONTENT_TYPE},
+    Method,
 };
+use reqwest::{header::HeaderMap, header::HeaderName, header::HeaderValue, IntoUrl, StatusCode};
+use reqwest::{RequestBuilder as ReqwestRequestBuilder, Response};
 
-#[cfg(all(feature = "multipart", feature = "blocking"))]
-use reqwest::blocking::multipart;
-
-#[cfg(all(not(feature = "blocking"), feature = "multipart"))]
-use reqwest::multipart;
+#[cfg(feature = "blocking")]
+use reqwest::blocking::{RequestBuilder as BlockingRequestBuilder, Response as BlockingResponse};
 
 use serde::Serialize;
 use url::Url;
 
 use crate::{
-    Error, OAuthParameters, SecretsProvider, SignResult, Signer, OAUTH_KEY_PREFIX, REALM_KEY,
+    plaintext::PLAINTEXT_METHOD_NAME, signer::SignedParameters, ClockSkew, DefaultSM, Error,
+    ExternalSigner, InnerRequestBuilder, OAuthParameters, ParameterTransmission, SecretsProvider,
+    SignResult, SignatureMethod, Signer, SignerError, SigningBackend, SigningReport,
+    OAUTH_CALLBACK_KEY, OAUTH_KEY_PREFIX, OAUTH_NONCE_KEY, OAUTH_TIMESTAMP_KEY, OAUTH_VERIFIER_KEY,
+    REALM_KEY,
 };
 
 /// Compatible interface with reqwest's [`RequestBuilder`](https://docs.rs/reqwest/0.10.8/reqwest/struct.RequestBuilder.html).
+///
+/// The `B` is the wrapped reqwest's request builder. The blocking one is
+/// available as `blocking::RequestBuilder`.
 #[derive(Debug)]
-pub struct RequestBuilder<TSigner>
+pub struct RequestBuilder<TSigner, B = ReqwestRequestBuilder>
 where
     TSigner: Clone,
 {
     method: Method,
-    inner: ReqwestRequestBuilder,
+    inner: B,
     signer: TSigner,
     url: Option<Url>,
     body: String,
+    raw_body: Option<RawBody>,
+    transmission: Option<ParameterTransmission>,
     query_oauth_parameters: HashMap<String, String>,
     form_oauth_parameters: HashMap<String, String>,
+    uncaptured_parameter: Option<String>,
+    skew: Option<ClockSkew>,
 }
 
-impl RequestBuilder<()> {
+/// The request body which is not captured as an urlencoded form.
+#[derive(Debug, Clone)]
+enum RawBody {
+    /// signed with the `oauth_body_hash`, or as the form when the
+    /// Content-Type is `application/x-www-form-urlencoded`
+    Bytes(Vec<u8>),
+    /// the body could not be read, so the request could not be signed
+    Stream,
+    /// the body is not signed (e.g. multipart, or failed to serialize)
+    Opaque,
+    /// the text parts of the multipart are signed as the form, but the files are not
+    #[cfg(feature = "multipart")]
+    Parts {
+        /// whether the multipart has the parts which are not signed, e.g. files
+        has_unsigned_parts: bool,
+    },
+}
+
+impl RawBody {
+    /// The bytes to compute the `oauth_body_hash`.
+    ///
+    /// # Errors
+    /// Fails when the body is a stream.
+    fn bytes_to_hash(raw_body: Option<&RawBody>) -> SignResult<Option<&[u8]>> {
+        match raw_body {
+            Some(RawBody::Bytes(bytes)) => Ok(Some(bytes)),
+            Some(RawBody::Stream) => Err(SignerError::UnreadableBody),
+            Some(RawBody::Opaque) => Ok(None),
+            #[cfg(feature = "multipart")]
+            Some(RawBody::Parts { .. }) => Ok(None),
+            None => Ok(None),
+        }
+    }
+}
+
+/// The clock skew correction of a request, shared by the async and the blocking `send`.
+#[derive(Debug)]
+struct SkewRetry<R> {
+    /// `None` when the clock skew is not corrected
+    skew: Option<ClockSkew>,
+    /// the request to sign and send again, taken at the first rejection
+    retry: Option<R>,
+}
+
+impl<R> SkewRetry<R> {
+    /// Learn the server clock from the response, and return the request to be
+    /// signed and sent again when its timestamp was rejected.
+    ///
+    /// The request is returned only once, so the retried request is never retried.
+    fn observe(&mut self, status: StatusCode, headers: &HeaderMap) -> Option<R> {
+        let skew = self.skew.as_ref()?;
+        if skew.observe(status, headers) {
+            self.retry.take()
+        } else {
+            None
+        }
+    }
+}
+
+impl<B> RequestBuilder<(), B>
+where
+    B: InnerRequestBuilder,
+{
     // ------------------------------------------------------------------------
     // Set signing information
 
@@ -61,7 +118,7 @@ impl RequestBuilder<()> {
     pub fn sign<'a, TSecrets>(
         self,
         secrets: TSecrets,
-    ) -> RequestBuilder<Signer<'a, TSecrets, DefaultSM>>
+    ) -> RequestBuilder<Signer<'a, TSecrets, DefaultSM>, B>
     where
         TSecrets: SecretsProvider + Clone,
     {
@@ -69,24 +126,228 @@ impl RequestBuilder<()> {
     }
 
     /// Add the signing information with OAuth parameters.
-    pub fn sign_with_params<'a, TSecrets, TSM>(
+    pub fn sign_with_params<TSecrets, TSM>(
+        self,
+        secrets: TSecrets,
+        params: OAuthParameters<'_, TSM>,
+    ) -> RequestBuilder<Signer<'_, TSecrets, TSM>, B>
+    where
+        TSecrets: SecretsProvider + Clone,
+        TSM: SignatureMethod + Clone,
+    {
+        RequestBuilder {
+            inner: self.inner,
+            method: self.method,
+            url: self.url,
+            body: self.body,
+            raw_body: self.raw_body,
+            transmission: self.transmission,
+            signer: Signer::new(secrets, params),
+            query_oauth_parameters: self.query_oauth_parameters,
+            form_oauth_parameters: self.form_oauth_parameters,
+            uncaptured_parameter: self.uncaptured_parameter,
+            skew: self.skew,
+        }
+    }
+}
+
+impl RequestBuilder<()> {
+    /// Add the signing information with the `SigningBackend`.
+    ///
+    /// The signature method specified in the `params` is ignored.
+    ///
+    /// # Note
+    /// The backend signs asynchronously, so this is not available for the
+    /// blocking `RequestBuilder`.
+    pub fn sign_with_backend<TSecrets, TSM, TBackend>(
         self,
         secrets: TSecrets,
-        params: OAuthParameters<'a, TSM>,
-    ) -> RequestBuilder<Signer<'a, TSecrets, TSM>>
+        params: OAuthParameters<'_, TSM>,
+        backend: TBackend,
+    ) -> RequestBuilder<ExternalSigner<'_, TSecrets, TBackend>>
     where
         TSecrets: SecretsProvider + Clone,
         TSM: SignatureMethod + Clone,
+        TBackend: SigningBackend,
     {
         RequestBuilder {
             inner: self.inner,
             method: self.method,
             url: self.url,
             body: self.body,
-            signer: Signer::new(secrets.into(), params),
+            raw_body: self.raw_body,
+            transmission: self.transmission,
+            signer: ExternalSigner::new(secrets, params, backend),
             query_oauth_parameters: self.query_oauth_parameters,
             form_oauth_parameters: self.form_oauth_parameters,
+            uncaptured_parameter: self.uncaptured_parameter,
+            skew: self.skew,
+        }
+    }
+}
+
+impl<'a, TSecrets, TSM, B> RequestBuilder<Signer<'a, TSecrets, TSM>, B>
+where
+    TSecrets: SecretsProvider + Clone,
+    TSM: SignatureMethod + Clone,
+    B: InnerRequestBuilder,
+{
+    // ------------------------------------------------------------------------
+    // Override OAuth parameters for this request
+
+    /// Set the `oauth_callback` value of this request.
+    ///
+    /// This overrides the value specified in the `OAuthParameters`.
+    pub fn oauth_callback<T>(self, callback: T) -> Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.override_parameter(OAUTH_CALLBACK_KEY, |p| p.callback(callback))
+    }
+
+    /// Set the `oauth_nonce` value of this request.
+    ///
+    /// This overrides the value specified in the `OAuthParameters`.
+    pub fn oauth_nonce<T>(self, nonce: T) -> Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.override_parameter(OAUTH_NONCE_KEY, |p| p.nonce(nonce))
+    }
+
+    /// Set the `oauth_timestamp` value of this request.
+    ///
+    /// This overrides the value specified in the `OAuthParameters`.
+    pub fn oauth_timestamp<T>(self, timestamp: T) -> Self
+    where
+        T: Into<u64>,
+    {
+        self.override_parameter(OAUTH_TIMESTAMP_KEY, |p| p.timestamp(timestamp))
+    }
+
+    /// Set the `oauth_verifier` value of this request.
+    ///
+    /// This overrides the value specified in the `OAuthParameters`.
+    pub fn oauth_verifier<T>(self, verifier: T) -> Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.override_parameter(OAUTH_VERIFIER_KEY, |p| p.verifier(verifier))
+    }
+
+    /// Set the `realm` value of this request.
+    ///
+    /// This overrides the value specified in the `OAuthParameters`.
+    pub fn realm<T>(self, realm: T) -> Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
+        self.override_parameter(REALM_KEY, |p| p.realm(realm))
+    }
+
+    /// Modify the OAuth parameters of this request.
+    ///
+    /// The parameter captured from the query or the form before is discarded,
+    /// so the last one wins.
+    fn override_parameter<F>(mut self, key: &str, f: F) -> Self
+    where
+        F: FnOnce(OAuthParameters<'a, TSM>) -> OAuthParameters<'a, TSM>,
+    {
+        self.query_oauth_parameters.remove(key);
+        self.form_oauth_parameters.remove(key);
+        RequestBuilder {
+            signer: self.signer.map_parameters(f),
+            ..self
+        }
+    }
+
+    // ------------------------------------------------------------------------
+    // Finish building the request with OAuth signature
+
+    /// Generate an OAuth signature and return the reqwest's `RequestBuilder`.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the signature could not be generated.
+    /// In the strict mode, it also fails if the request could not be signed
+    /// entirely, e.g. the URL could not be parsed.
+    pub fn generate_signature(self) -> SignResult<B> {
+        if self.signer.is_strict() {
+            self.check_strict()?;
+        }
+        let (inner, signer, target) = self.into_sign_target();
+        if let Some(target) = target {
+            let signed = signer
+                .override_oauth_parameter(target.oauth_parameters)
+                .sign_parameters(
+                    target.method.as_str(),
+                    target.url.as_str(),
+                    &target.payload,
+                    RawBody::bytes_to_hash(target.raw_body.as_ref())?,
+                )?;
+            // put OAuth parameters into inner RequestBuilder and return it
+            transmit(
+                inner,
+                signed,
+                target.transmission,
+                target.form,
+                target.stripped_form,
+            )
+        } else {
+            // just return inner RequestBuilder
+            Ok(inner)
+        }
+    }
+    /// Generate the report of the OAuth signing without sending the request.
+    ///
+    /// The report includes the signature base string and the parameters,
+    /// so you can compare them with the documents provided by the server.
+    ///
+    /// # Note
+    /// When the nonce or the timestamp are not specified, they are generated
+    /// for each call, so the report may differ from the request sent later.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the URL could not be parsed, or the signature
+    /// could not be generated.
+    pub fn signing_report(&self) -> SignResult<SigningReport> {
+        if self.signer.is_strict() {
+            self.check_strict()?;
         }
+        let target = self.sign_target().ok_or(SignerError::InvalidUrl)?;
+        let signed = self
+            .signer
+            .clone()
+            .override_oauth_parameter(target.oauth_parameters)
+            .sign_parameters(
+                target.method.as_str(),
+                target.url.as_str(),
+                &target.payload,
+                RawBody::bytes_to_hash(target.raw_body.as_ref())?,
+            )?;
+        Ok(signed.report())
+    }
+
+    /// Generate the signed URL without sending the request.
+    ///
+    /// The `oauth_*` parameters and `oauth_signature` are added to the query,
+    /// so the URL can be fetched later by another party without the secrets.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the URL could not be parsed, or the signature
+    /// could not be generated.
+    /// It also fails with `SignerError::UnpresignableRequest` for the request
+    /// other than `GET`, the request with the body, or the PLAINTEXT signature
+    /// method (whose signature is the secrets themselves).
+    pub fn presigned_url(self) -> Result<Url, Error> {
+        self.check_presignable(self.signer.signature_method_name())?;
+        let (url, _) = self
+            .transmission(ParameterTransmission::QueryString)
+            .generate_signature()?
+            .build_parts()?;
+        Ok(url)
     }
 }
 
@@ -99,103 +360,413 @@ where
     // Finish building the request and send it to server with OAuth signature
 
     /// Constructs the Request and sends it to the target URL, returning a
-    /// Response.
+    /// future Response.
     ///
     /// # Errors
     ///
     /// This method fails if there was an error while sending request,
     /// redirect loop was detected or redirect limit was exhausted.
-    #[cfg(feature = "blocking")]
-    pub fn send(self) -> Result<Response, Error> {
-        Ok(self.generate_signature()?.send()?)
+    pub async fn send(self) -> Result<Response, Error> {
+        let mut retry = self.skew_retry(self.signer.is_pinned());
+        let mut response = self.generate_signature()?.send().await?;
+        if let Some(request) = retry.observe(response.status(), response.headers()) {
+            // sign again with the corrected timestamp
+            response = request.generate_signature()?.send().await?;
+            retry.observe(response.status(), response.headers());
+        }
+        Ok(response)
     }
-    
+}
+
+#[cfg(feature = "blocking")]
+impl<TSecrets, TSM> RequestBuilder<Signer<'_, TSecrets, TSM>, BlockingRequestBuilder>
+where
+    TSecrets: SecretsProvider + Clone,
+    TSM: SignatureMethod + Clone,
+{
+    // ------------------------------------------------------------------------
+    // Finish building the request and send it to server with OAuth signature
+
     /// Constructs the Request and sends it to the target URL, returning a
-    /// future Response.
+    /// Response.
     ///
     /// # Errors
     ///
     /// This method fails if there was an error while sending request,
     /// redirect loop was detected or redirect limit was exhausted.
-    #[cfg(not(feature = "blocking"))]
-    pub async fn send(self) -> Result<Response, Error> {
-        Ok(self.generate_signature()?.send().await?)
+    pub fn send(self) -> Result<BlockingResponse, Error> {
+        let mut retry = self.skew_retry(self.signer.is_pinned());
+        let mut response = self.generate_signature()?.send()?;
+        if let Some(request) = retry.observe(response.status(), response.headers()) {
+            // sign again with the corrected timestamp
+            response = request.generate_signature()?.send()?;
+            retry.observe(response.status(), response.headers());
+        }
+        Ok(response)
     }
+}
 
-    /// Generate an OAuth signature and return the reqwest's `RequestBuilder`.
-    pub fn generate_signature(self) -> SignResult<ReqwestRequestBuilder> {
-        if let Some(url) = self.url {
-            let (is_q, url, payload) = match url.query() {
-                None | Some("") => {
-                    // POST
-                    (false, url, self.body.as_ref())
-                }
-                Some(q) => {
-                    // GET
-                    let mut pure_url = url.clone();
-                    pure_url.set_query(None);
-                    (true, pure_url, q)
-                }
-            };
-            let oauth_params: HashMap<String, String> = self
-                .form_oauth_parameters
-                .into_iter()
-                .chain(self.query_oauth_parameters.into_iter())
-                .collect();
+impl<TSecrets, TBackend> RequestBuilder<ExternalSigner<'_, TSecrets, TBackend>>
+where
+    TSecrets: SecretsProvider + Clone,
+    TBackend: SigningBackend,
+{
+    // ------------------------------------------------------------------------
+    // Finish building the request with OAuth signature
 
-            let signature = self
-                .signer
-                .override_oauth_parameter(oauth_params)
-                .generate_signature(self.method, url, payload, is_q)?;
-            // println!("generated signature: {}", signature);
-            // set AUTHORIZATION header to inner RequestBuilder and return it
-            Ok(self.inner.header(AUTHORIZATION, signature))
+    /// Generate an OAuth signature with the `SigningBackend` and return the
+    /// reqwest's `RequestBuilder`.
+    pub async fn generate_signature(self) -> SignResult<ReqwestRequestBuilder> {
+        if self.signer.is_strict() {
+            self.check_strict()?;
+        }
+        let (inner, signer, target) = self.into_sign_target();
+        if let Some(target) = target {
+            let signed = signer
+                .override_oauth_parameter(target.oauth_parameters)
+                .sign_parameters(
+                    target.method,
+                    target.url,
+                    &target.payload,
+                    RawBody::bytes_to_hash(target.raw_body.as_ref())?,
+                )
+                .await?;
+            transmit(
+                inner,
+                signed,
+                target.transmission,
+                target.form,
+                target.stripped_form,
+            )
         } else {
-            // just return inner RequestBuilder
-            Ok(self.inner)
+            Ok(inner)
+        }
+    }
+    /// Generate the signed URL with the `SigningBackend`, without sending the
+    /// request.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the URL could not be parsed, or the backend
+    /// failed to sign.
+    /// It also fails with `SignerError::UnpresignableRequest` as same as the
+    /// `presigned_url` of the `Signer`.
+    pub async fn presigned_url(self) -> Result<Url, Error> {
+        self.check_presignable(Some(self.signer.signature_method_name()))?;
+        let (url, _) = self
+            .transmission(ParameterTransmission::QueryString)
+            .generate_signature()
+            .await?
+            .build_parts()?;
+        Ok(url)
+    }
+}
+
+impl<TSecrets, TBackend> RequestBuilder<ExternalSigner<'_, TSecrets, TBackend>>
+where
+    TSecrets: SecretsProvider + Clone,
+    TBackend: SigningBackend,
+{
+    // ------------------------------------------------------------------------
+    // Finish building the request and send it to server with OAuth signature
+
+    /// Constructs the Request and sends it to the target URL, returning a
+    /// future Response.
+    ///
+    /// The signature is generated by the `SigningBackend` before sending.
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the backend failed to sign, there was an error
+    /// while sending request, redirect loop was detected or redirect limit
+    /// was exhausted.
+    pub async fn send(self) -> Result<Response, Error> {
+        let mut retry = self.skew_retry(self.signer.is_pinned());
+        let mut response = self.generate_signature().await?.send().await?;
+        if let Some(request) = retry.observe(response.status(), response.headers()) {
+            // sign again with the corrected timestamp
+            response = request.generate_signature().await?.send().await?;
+            retry.observe(response.status(), response.headers());
+        }
+        Ok(response)
+    }
+}
+
+/// Put the signed OAuth parameters into the request with the transmission mode.
+///
+/// The `transmission` overrides the mode configured with the `OAuthParameters`.
+/// The `stripped_form` replaces the raw form body, whose `oauth_*` parameters are captured.
+fn transmit<B: InnerRequestBuilder>(
+    inner: B,
+    signed: SignedParameters,
+    transmission: Option<ParameterTransmission>,
+    form: Option<String>,
+    stripped_form: Option<String>,
+) -> SignResult<B> {
+    let inner = match stripped_form {
+        Some(form) => inner.body(form.into()),
+        None => inner,
+    };
+    match transmission.unwrap_or_else(|| signed.transmission()) {
+        ParameterTransmission::AuthorizationHeader => {
+            Ok(inner.header(AUTHORIZATION, signed.authorization_header()))
+        }
+        ParameterTransmission::QueryString => Ok(inner.query(&signed.parameter_pairs())),
+        ParameterTransmission::FormBody => {
+            let form = form.ok_or(SignerError::FormBodyUnavailable)?;
+            let body = url::form_urlencoded::Serializer::new(form)
+                .extend_pairs(signed.parameter_pairs())
+                .finish();
+            // the form body may be empty, so Content-Type is not set yet
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                CONTENT_TYPE,
+                HeaderValue::from_static("application/x-www-form-urlencoded"),
+            );
+            Ok(inner.headers(headers).body(body.into()))
         }
     }
 }
 
-impl<TSigner> RequestBuilder<TSigner>
+/// Check the Content-Type header of the request is `application/x-www-form-urlencoded`.
+///
+/// The request could be built only when the body is not a stream.
+fn has_form_content_type<B: InnerRequestBuilder>(inner: &B) -> bool {
+    inner
+        .try_clone()
+        .and_then(|builder| builder.build_parts().ok())
+        .and_then(|(_, headers)| {
+            let content_type = headers.get(CONTENT_TYPE)?.to_str().ok()?;
+            // strip parameters, e.g. `; charset=utf-8`
+            let essence = content_type.split(';').next().unwrap_or_default().trim();
+            Some(essence.eq_ignore_ascii_case("application/x-www-form-urlencoded"))
+        })
+        .unwrap_or(false)
+}
+
+/// Captured request information to be signed.
+struct SignTarget {
+    method: Method,
+    url: Url,
+    payload: String,
+    form: Option<String>,
+    raw_body: Option<RawBody>,
+    /// the raw form body without the captured `oauth_*` parameters, to be sent instead
+    stripped_form: Option<String>,
+    transmission: Option<ParameterTransmission>,
+    oauth_parameters: HashMap<String, String>,
+}
+
+impl<TSigner, B> RequestBuilder<TSigner, B>
 where
     TSigner: Clone,
+    B: InnerRequestBuilder,
 {
     pub(crate) fn new<T: IntoUrl + Clone>(
-        client: &ReqwestClient,
+        client: &B::Client,
         method: Method,
         url: T,
         signer: TSigner,
+        skew: Option<ClockSkew>,
     ) -> Self {
         match url.clone().into_url() {
             Ok(url) => {
                 let mut query_oauth_params: HashMap<String, String> = HashMap::new();
                 let stealed_url = steal_oauth_params_from_url(url, &mut query_oauth_params);
                 RequestBuilder {
-                    inner: client.request(method.clone(), stealed_url.clone()),
+                    inner: B::request(client, method.clone(), stealed_url.clone()),
                     method,
                     url: Some(stealed_url),
                     body: String::new(),
-                    signer: signer,
+                    raw_body: None,
+                    transmission: None,
+                    signer,
                     query_oauth_parameters: query_oauth_params,
                     form_oauth_parameters: HashMap::new(),
+                    uncaptured_parameter: None,
+                    skew,
                 }
             }
             Err(_) => RequestBuilder {
-                inner: client.request(method.clone(), url),
+                inner: B::request(client, method.clone(), url),
                 method,
                 url: None,
                 body: String::new(),
-                signer: signer,
+                raw_body: None,
+                transmission: None,
+                signer,
                 query_oauth_parameters: HashMap::new(),
                 form_oauth_parameters: HashMap::new(),
+                uncaptured_parameter: None,
+                skew,
             },
         }
     }
 
+    /// Ensure the whole request is covered by the signature, for the strict mode.
+    fn check_strict(&self) -> SignResult<()> {
+        if self.url.is_none() {
+            return Err(SignerError::InvalidUrl);
+        }
+        if let Some(ref key) = self.uncaptured_parameter {
+            return Err(SignerError::UnsignedParameter(key.clone()));
+        }
+        match self.raw_body {
+            Some(RawBody::Opaque) => Err(SignerError::UnsignedBody),
+            #[cfg(feature = "multipart")]
+            Some(RawBody::Parts {
+                has_unsigned_parts: true,
+            }) => Err(SignerError::UnsignedBody),
+            _ => Ok(()),
+        }
+    }
+
+    /// Destructure the builder into the inner builder, the signer, and the
+    /// captured information to be signed.
+    ///
+    /// The target is `None` when the URL could not be parsed.
+    fn into_sign_target(self) -> (B, TSigner, Option<SignTarget>) {
+        let target = self.sign_target();
+        (self.inner, self.signer, target)
+    }
+
+    /// Ensure the request could be handed out as the pre-signed URL: only the
+    /// `GET` request without the body, since the recipient could not reproduce
+    /// the others, and never with PLAINTEXT, whose signature is the secrets.
+    fn check_presignable(&self, signature_method: Option<&str>) -> SignResult<()> {
+        if signature_method == Some(PLAINTEXT_METHOD_NAME) {
+            return Err(SignerError::UnpresignableRequest(
+                "PLAINTEXT signature reveals the secrets".to_string(),
+            ));
+        }
+        if self.method != Method::GET {
+            return Err(SignerError::UnpresignableRequest(format!(
+                "{} request",
+                self.method
+            )));
+        }
+        if !self.body.is_empty() || self.raw_body.is_some() {
+            return Err(SignerError::UnpresignableRequest(
+                "the request has the body".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Prepare the clock skew correction of the request before it is sent.
+    ///
+    /// The `pinned` is whether the signer fixes the `oauth_timestamp` or the `oauth_nonce`.
+    fn skew_retry(&self, pinned: bool) -> SkewRetry<Self> {
+        SkewRetry {
+            retry: self
+                .skew
+                .as_ref()
+                .and_then(|_| self.try_clone_to_retry(pinned)),
+            skew: self.skew.clone(),
+        }
+    }
+
+    /// Clone the builder to sign and send again, when the timestamp is rejected.
+    ///
+    /// `None` when the `oauth_timestamp` or the `oauth_nonce` is fixed by the
+    /// signer (`pinned`) or captured, since the same values are rejected again.
+    fn try_clone_to_retry(&self, pinned: bool) -> Option<Self> {
+        let captured = [OAUTH_TIMESTAMP_KEY, OAUTH_NONCE_KEY].iter().any(|key| {
+            self.query_oauth_parameters.contains_key(*key)
+                || self.form_oauth_parameters.contains_key(*key)
+        });
+        if pinned || captured {
+            None
+        } else {
+            self.try_clone()
+        }
+    }
+
+    /// Collect the captured information to be signed.
+    ///
+    /// The target is `None` when the URL could not be parsed.
+    fn sign_target(&self) -> Option<SignTarget> {
+        // the pre-encoded form may be supplied with `body()`, then sign it as the form
+        // and capture its oauth_* parameters as same as `form()`
+        let mut raw_form_oauth_parameters = HashMap::new();
+        let mut stripped_form = None;
+        let (body, raw_body) = match self.raw_body {
+            Some(RawBody::Bytes(ref bytes)) if has_form_content_type(&self.inner) => {
+                match String::from_utf8(bytes.clone()) {
+                    Ok(form) => {
+                        let remainder =
+                            steal_oauth_params_core(&form, &mut raw_form_oauth_parameters);
+                        if raw_form_oauth_parameters.is_empty() {
+                            (form, None)
+                        } else {
+                            let form = url::form_urlencoded::Serializer::new(String::new())
+                                .extend_pairs(remainder)
+                                .finish();
+                            stripped_form = Some(form.clone());
+                            (form, None)
+                        }
+                    }
+                    Err(e) => (self.body.clone(), Some(RawBody::Bytes(e.into_bytes()))),
+                }
+            }
+            ref raw_body => (self.body.clone(), raw_body.clone()),
+        };
+        // the urlencoded form body, which can carry OAuth parameters
+        let form = match raw_body {
+            None => Some(body.clone()),
+            Some(_) => None,
+        };
+        self.url.clone().map(|url| {
+            // both of the query and the form body are signed, regardless of the method
+            // cf. https://tools.ietf.org/html/rfc5849#section-3.4.1.3.1
+            let (url, payload) = match url.query() {
+                None | Some("") => (url, body),
+                Some(q) => {
+                    let payload = if body.is_empty() {
+                        q.to_string()
+                    } else {
+                        format!("{}&{}", q, body)
+                    };
+                    let mut pure_url = url;
+                    pure_url.set_query(None);
+                    (pure_url, payload)
+                }
+            };
+            let oauth_parameters = self
+                .form_oauth_parameters
+                .clone()
+                .into_iter()
+                .chain(raw_form_oauth_parameters)
+                .chain(self.query_oauth_parameters.clone())
+                .collect();
+            SignTarget {
+                method: self.method.clone(),
+                url,
+                payload,
+                form,
+                raw_body,
+                stripped_form,
+                transmission: self.transmission,
+                oauth_parameters,
+            }
+        })
+    }
+
     // ------------------------------------------------------------------------
     // Trapped with the wrapper
 
+    /// Set how the OAuth parameters of this request are transmitted.
+    ///
+    /// This overrides the transmission mode specified in the `OAuthParameters`.
+    ///
+    /// # Note
+    /// `ParameterTransmission::FormBody` is available only when the request
+    /// body is an urlencoded form or empty, otherwise the signing fails.
+    pub fn transmission(mut self, transmission: ParameterTransmission) -> Self {
+        self.transmission = Some(transmission);
+        self
+    }
+
     /// Modify the query string of the URL.
     ///
     /// Modifies the URL of this request, adding the parameters provided.
@@ -240,13 +811,20 @@ where
     pub fn form<T: Serialize + ?Sized + Clone>(mut self, form: &T) -> Self {
         // before stealing oauth_* parameters, clear old result
         self.form_oauth_parameters.clear();
+        // reqwest reports the error, and the old body must not be signed
+        if serde_urlencoded::to_string(form).is_err() {
+            self.body = String::new();
+            self.raw_body = Some(RawBody::Opaque);
+            return self.pass_through(|b| b.form(form));
+        }
         // stealing oauth_* parameters
-        let form = steal_oauth_params(form, &mut self.query_oauth_parameters);
+        let form = steal_oauth_params(form, &mut self.form_oauth_parameters);
 
         match serde_urlencoded::to_string(form.clone()) {
             Ok(body) => {
                 self.inner = self.inner.form(&form);
                 self.body = body;
+                self.raw_body = None;
                 self
             }
             Err(_) => self.pass_through(|b| b.form(&form)),
@@ -268,10 +846,17 @@ where
     /// # }
     /// ```
     ///
-    /// Note: application/json is not handled by the OAuth signer.
+    /// Note: the OAuth signer signs the `oauth_body_hash` of the serialized JSON.
     #[cfg(feature = "json")]
-    pub fn json<T: Serialize + ?Sized>(self, json: &T) -> Self {
-        self.pass_through(|b: ReqwestRequestBuilder| b.json(json))
+    pub fn json<T: Serialize + ?Sized>(mut self, json: &T) -> Self {
+        self.form_oauth_parameters.clear();
+        self.body = String::new();
+        // when serialization fails, reqwest reports the error
+        self.raw_body = Some(match serde_json::to_vec(json) {
+            Ok(bytes) => RawBody::Bytes(bytes),
+            Err(_) => RawBody::Opaque,
+        });
+        self.pass_through(|b| b.json(json))
     }
 
     // ------------------------------------------------------------------------
@@ -282,7 +867,10 @@ where
     /// # Note
     /// Generated OAuth signature will may be invalid when you call this method
     /// with the parameters including the `oauth_*` parameters or the `realm` parameter.
-    pub fn query_without_capture<T: Serialize>(self, query: &T) -> Self {
+    ///
+    /// In the strict mode, the signing fails with `SignerError::UnsignedParameter` then.
+    pub fn query_without_capture<T: Serialize>(mut self, query: &T) -> Self {
+        self.record_uncaptured_parameter(query);
         self.pass_through(|b| b.query(query))
     }
 
@@ -290,16 +878,33 @@ where
     /// # Note
     /// Generated OAuth signature will may be invalid when you call this method
     /// with the parameters including the `oauth_*` parameters or the `realm` parameter.
-    pub fn form_without_capture<T: Serialize + ?Sized>(self, form: &T) -> Self {
+    ///
+    /// In the strict mode, the signing fails with `SignerError::UnsignedParameter` then.
+    pub fn form_without_capture<T: Serialize + ?Sized>(mut self, form: &T) -> Self {
+        self.record_uncaptured_parameter(form);
         self.pass_through(|b| b.form(form))
     }
 
+    /// Remember the first `oauth_*` or `realm` parameter which is sent without being captured.
+    fn record_uncaptured_parameter<T: Serialize + ?Sized>(&mut self, parameters: &T) {
+        if self.uncaptured_parameter.is_some() {
+            return;
+        }
+        let Ok(encoded) = serde_urlencoded::to_string(parameters) else {
+            return;
+        };
+        self.uncaptured_parameter = url::form_urlencoded::parse(encoded.as_bytes())
+            .map(|(k, _)| k)
+            .find(|k| k.starts_with(OAUTH_KEY_PREFIX) || k == REALM_KEY)
+            .map(|k| k.into_owned());
+    }
+
     // ------------------------------------------------------------------------
     // Pass-through to inner builder
 
     fn pass_through<F>(self, f: F) -> Self
     where
-        F: FnOnce(ReqwestRequestBuilder) -> ReqwestRequestBuilder,
+        F: FnOnce(B) -> B,
     {
         RequestBuilder {
             inner: f(self.inner),
@@ -344,7 +949,24 @@ where
     }
 
     /// Set the request body.
-    pub fn body<T: Into<Body>>(mut self, body: T) -> Self {
+    ///
+    /// # Note
+    /// When the `Content-Type` header is `application/x-www-form-urlencoded`,
+    /// the OAuth signer signs the body as the form.
+    /// Otherwise, the OAuth signer signs the `oauth_body_hash` of the body.
+    ///
+    /// The `oauth_*` parameters in the urlencoded body are captured and signed
+    /// as same as `form()`, and they are removed from the body.
+    ///
+    /// The signing fails when the body is a stream, because it could not be read.
+    pub fn body<T: Into<B::Body>>(mut self, body: T) -> Self {
+        let body = body.into();
+        self.form_oauth_parameters.clear();
+        self.body = String::new();
+        self.raw_body = Some(match B::body_bytes(&body) {
+            Some(bytes) => RawBody::Bytes(bytes.to_vec()),
+            None => RawBody::Stream,
+        });
         self.inner = self.inner.body(body);
         self
     }
@@ -379,10 +1001,25 @@ where
     /// # }
     /// ```
     ///
-    /// Note: multipart/form-data is not handled by the OAuth signer.
+    /// Note: multipart/form-data is not handled by the OAuth signer,
+    /// unless you pass the `MultipartForm` with `sign_text_parts(true)`.
+    /// Then its text parts are signed, but the file parts are not.
+    /// The `oauth_*` text parts of the `MultipartForm` are always captured
+    /// as the OAuth parameters, instead of being sent as the parts.
     #[cfg(feature = "multipart")]
-    pub fn multipart(self, multipart: multipart::Form) -> Self {
-        self.pass_through(|b| b.multipart(multipart))
+    pub fn multipart<T: Into<B::MultipartForm>>(mut self, multipart: T) -> Self {
+        let (inner, parts) = self.inner.multipart(multipart.into());
+        self.inner = inner;
+        // the oauth_* text parts are captured as same as the form
+        self.form_oauth_parameters = parts.oauth_parameters;
+        self.raw_body = Some(match parts.signed_text_parts {
+            Some(_) => RawBody::Parts {
+                has_unsigned_parts: parts.has_unsigned_parts,
+            },
+            None => RawBody::Opaque,
+        });
+        self.body = parts.signed_text_parts.unwrap_or_default();
+        self
     }
 
     /// Disable CORS on fetching the request.
@@ -403,18 +1040,19 @@ where
     /// `None` is returned if the RequestBuilder can not be cloned,
     /// i.e. if the request body is a stream.
     pub fn try_clone(&self) -> Option<Self> {
-        match self.inner.try_clone() {
-            Some(inner) => Some(RequestBuilder {
-                inner,
-                method: self.method.clone(),
-                url: self.url.clone(),
-                body: self.body.clone(),
-                signer: self.signer.clone(),
-                query_oauth_parameters: self.query_oauth_parameters.clone(),
-                form_oauth_parameters: self.form_oauth_parameters.clone(),
-            }),
-            None => None,
-        }
+        self.inner.try_clone().map(|inner| RequestBuilder {
+            inner,
+            method: self.method.clone(),
+            url: self.url.clone(),
+            body: self.body.clone(),
+            raw_body: self.raw_body.clone(),
+            transmission: self.transmission,
+            signer: self.signer.clone(),
+            query_oauth_parameters: self.query_oauth_parameters.clone(),
+            form_oauth_parameters: self.form_oauth_parameters.clone(),
+            uncaptured_parameter: self.uncaptured_parameter.clone(),
+            skew: self.skew.clone(),
+        })
     }
 }
 
@@ -435,17 +1073,17 @@ where
     }
 
     // capture oauth_* item and construct remainder vector, then return
-    steal_oauth_params_core(&empty_url, oauth_map)
+    steal_oauth_params_core(empty_url.query().unwrap_or_default(), oauth_map)
         .into_iter()
         .map(|(k, v)| (k.to_string(), v.to_string()))
         .collect()
 }
 
 fn steal_oauth_params_from_url(mut url: Url, oauth_map: &mut HashMap<String, String>) -> Url {
-    let remainder = steal_oauth_params_core(&url, oauth_map);
+    let remainder = steal_oauth_params_core(url.query().unwrap_or_default(), oauth_map);
     // clear query
     url.set_query(None);
-    if remainder.len() > 0 {
+    if !remainder.is_empty() {
         // add non oauth_* parameters
         let mut serializer = url.query_pairs_mut();
         for (k, v) in remainder {
@@ -457,12 +1095,11 @@ fn steal_oauth_params_from_url(mut url: Url, oauth_map: &mut HashMap<String, Str
 }
 
 fn steal_oauth_params_core(
-    url: &Url,
+    query: &str,
     oauth_map: &mut HashMap<String, String>,
 ) -> Vec<(String, String)> {
     // steal oauth_* items
-    url.query_pairs()
-        .into_iter()
+    url::form_urlencoded::parse(query.as_bytes())
         .map(|(k, v)| (k.to_string(), v.to_string()))
         .filter_map(|(k, v)| {
             if k.starts_with(OAUTH_KEY_PREFIX) || k == REALM_KEY {
@@ -478,22 +1115,30 @@ fn steal_oauth_params_core(
 
 #[cfg(test)]
 mod tests {
-    use http::header::AUTHORIZATION;
+    use http::header::{AUTHORIZATION, CONTENT_TYPE};
+    use reqwest::header::{HeaderMap, HeaderValue};
 
-    #[cfg(feature = "blocking")]
-    use reqwest::blocking::{
-        RequestBuilder as ReqwestRequestBuilder, Response,
-        Client as ReqwestClient, 
-    };
+    use reqwest::Client as ReqwestClient;
 
-    #[cfg(not(feature = "blocking"))]
-    use reqwest::{
-        RequestBuilder as ReqwestRequestBuilder, Response,
-        Client as ReqwestClient,
+    use crate::{
+        DefaultSM, FixedClock, HmacSha256, HmacSha512, OAuthBackendProvider, OAuthClientProvider,
+        OAuthParameters, ParameterTransmission, Plaintext, Secrets, SequenceNonce, Signer,
+        SignerError, OAUTH_NONCE_KEY, OAUTH_TIMESTAMP_KEY,
     };
 
+    use crate::{BackendError, Client, ClockSkew, LocalSigningBackend, SigningBackend};
+
+    #[cfg(feature = "multipart")]
+    use super::RequestBuilder;
+    #[cfg(feature = "multipart")]
+    use crate::MultipartForm;
+    #[cfg(feature = "multipart")]
+    use reqwest::multipart;
+
+    #[cfg(feature = "rsa-sha1")]
     use crate::{
-        OAuthClientProvider, OAuthParameters, Secrets, OAUTH_NONCE_KEY, OAUTH_TIMESTAMP_KEY,
+        rsa_sha1::tests::{EXPECTED_SIGNATURE, PKCS8_PEM},
+        RsaSha1,
     };
 
     fn extract_signature(auth_header: &str) -> String {
@@ -765,7 +1410,1249 @@ mod tests {
         assert_eq!(
             extract_signature(sign.unwrap().to_str().unwrap()),
             "hCtSmYh+iHYCEqBWrE7C7hYmtUk="
-        ); 
+        );
+    }
+
+    #[test]
+    fn sign_post_query_and_body() {
+        // the request of https://tools.ietf.org/html/rfc5849#section-3.4.1
+        // expected signature is computed with Python's hmac module
+        let endpoint = "http://example.com/request?b5=%3D%253D&a3=a&c%40=&a2=r%20b";
+        let secrets = Secrets::new("9djdj82h48djs9d2", "j49sj3j29djd")
+            .token("kkk9d7dh3k39sjv7", "dh893hdasih9");
+        let params = OAuthParameters::new()
+            .nonce("7d8f3e4a")
+            .timestamp(137_131_201u64)
+            .realm("Example");
+
+        let req = ReqwestClient::new()
+            .oauth1_with_params(secrets, params)
+            .post(endpoint)
+            .form(&[("c2", ""), ("a3", "2 q")])
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let sign = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert_eq!(extract_signature(sign), "KLQOrg89+C0MYbbmA9+bUKpoVhg=");
+    }
+
+    #[test]
+    fn capture_form_oauth_params() {
+        let req = ReqwestClient::new()
+            .oauth1(Secrets::new("consumer", "secret"))
+            .post("https://example.com/?a=b")
+            .form(&[("c", "d"), ("oauth_nonce", "FormNonce")]);
+        assert_eq!(
+            req.form_oauth_parameters
+                .get("oauth_nonce")
+                .map(String::as_str),
+            Some("FormNonce")
+        );
+        assert!(req.query_oauth_parameters.is_empty());
+
+        let sign = req.generate_signature().unwrap().build().unwrap();
+        let sign = sign.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert!(sign.contains("oauth_nonce=\"FormNonce\""));
+    }
+
+    #[test]
+    fn generate_nonce_for_each_request() {
+        let params = OAuthParameters::new()
+            .clock(FixedClock(137_131_201))
+            .nonce_generator(SequenceNonce::new("nonce-"));
+        let client =
+            ReqwestClient::new().oauth1_with_params(Secrets::new("consumer", "secret"), params);
+
+        let headers: Vec<String> = (0..2)
+            .map(|_| {
+                let req = client
+                    .get("https://example.com/")
+                    .generate_signature()
+                    .unwrap()
+                    .build()
+                    .unwrap();
+                req.headers()[AUTHORIZATION].to_str().unwrap().to_string()
+            })
+            .collect();
+        assert!(headers[0].contains("oauth_nonce=\"nonce-0\""));
+        assert!(headers[1].contains("oauth_nonce=\"nonce-1\""));
+        assert!(headers
+            .iter()
+            .all(|header| header.contains("oauth_timestamp=\"137131201\"")));
+    }
+
+    #[test]
+    fn pinned_nonce_supersedes_generator() {
+        let params = OAuthParameters::new()
+            .nonce("PinnedNonce")
+            .timestamp(100u64)
+            .clock(FixedClock(137_131_201))
+            .nonce_generator(SequenceNonce::new("nonce-"));
+        let req = ReqwestClient::new()
+            .oauth1_with_params(Secrets::new("consumer", "secret"), params)
+            .get("https://example.com/")
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+        let sign = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert!(sign.contains("oauth_nonce=\"PinnedNonce\""));
+        assert!(sign.contains("oauth_timestamp=\"100\""));
+    }
+
+    #[test]
+    fn override_oauth_parameters_per_request() {
+        let params = OAuthParameters::new()
+            .callback("https://example.com/client")
+            .nonce("ClientNonce")
+            .timestamp(100u64)
+            .verifier("ClientVerifier")
+            .realm("Client");
+        let req = ReqwestClient::new()
+            .oauth1_with_params(Secrets::new("consumer", "secret"), params)
+            .get("https://example.com/")
+            .oauth_callback("oob")
+            .oauth_nonce("RequestNonce")
+            .oauth_timestamp(200u64)
+            .oauth_verifier("RequestVerifier")
+            .realm("Request")
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+        let sign = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert!(sign.contains("oauth_callback=\"oob\""));
+        assert!(sign.contains("oauth_nonce=\"RequestNonce\""));
+        assert!(sign.contains("oauth_timestamp=\"200\""));
+        assert!(sign.contains("oauth_verifier=\"RequestVerifier\""));
+        assert!(sign.contains("realm=\"Request\""));
+    }
+
+    #[test]
+    fn last_oauth_parameter_wins() {
+        let client = ReqwestClient::new().oauth1(Secrets::new("consumer", "secret"));
+        let verifier_of = |headers: &HeaderMap| {
+            let sign = headers.get(AUTHORIZATION).unwrap().to_str().unwrap();
+            sign.contains("oauth_verifier=\"typed\"")
+        };
+
+        let req = client
+            .get("https://example.com/")
+            .query(&[("oauth_verifier", "query")])
+            .oauth_verifier("typed")
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(verifier_of(req.headers()));
+
+        let req = client
+            .get("https://example.com/")
+            .oauth_verifier("typed")
+            .query(&[("oauth_verifier", "query")])
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(!verifier_of(req.headers()));
+    }
+
+    /// Serve the responses of the `statuses` with the `Date` header in order,
+    /// and return the endpoint and the received requests.
+    async fn serve_dated(
+        statuses: &'static [&'static str],
+    ) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        let responses = statuses
+            .iter()
+            .map(|status| {
+                format!(
+                    "HTTP/1.1 {}\r\nDate: Sun, 06 Nov 1994 08:49:37 GMT\r\n\
+                     Content-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                )
+            })
+            .collect();
+        let (base, server) = crate::test_server::serve(responses).await;
+        (format!("{}/photos", base), server)
+    }
+
+    #[tokio::test]
+    async fn retry_with_corrected_timestamp() {
+        let (endpoint, server) = serve_dated(&["401 Unauthorized", "200 OK"]).await;
+
+        let skew = ClockSkew::with_clock(FixedClock(784_000_000));
+        let params = OAuthParameters::new().nonce_generator(SequenceNonce::new("nonce-"));
+        let resp = ReqwestClient::builder()
+            .no_proxy()
+            .build()
+            .unwrap()
+            .oauth1_with_params(Secrets::new("consumer", "secret"), params)
+            .correct_clock_skew(skew.clone())
+            .get(&endpoint)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(resp.status(), reqwest::StatusCode::OK);
+        assert_eq!(skew.offset(), 111_777);
+        let requests = server.await.unwrap();
+        assert!(requests[0].contains("oauth_timestamp=\"784000000\""));
+        assert!(requests[1].contains("oauth_nonce=\"nonce-1\""));
+        assert!(requests[1].contains("oauth_timestamp=\"784111777\""));
+    }
+
+    #[tokio::test]
+    async fn retry_with_backend() {
+        let (endpoint, server) = serve_dated(&["401 Unauthorized", "200 OK"]).await;
+
+        let skew = ClockSkew::with_clock(FixedClock(784_000_000));
+        let params = OAuthParameters::new().nonce_generator(SequenceNonce::new("nonce-"));
+        let resp = ReqwestClient::builder()
+            .no_proxy()
+            .build()
+            .unwrap()
+            .oauth1_with_backend(
+                Secrets::new("consumer", "secret"),
+                params,
+                LocalSigningBackend::hmac_sha1("secret", None),
+            )
+            .correct_clock_skew(skew.clone())
+            .get(&endpoint)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(resp.status(), reqwest::StatusCode::OK);
+        assert_eq!(skew.offset(), 111_777);
+        let requests = server.await.unwrap();
+        assert!(requests[0].contains("oauth_timestamp=\"784000000\""));
+        assert!(requests[1].contains("oauth_nonce=\"nonce-1\""));
+        assert!(requests[1].contains("oauth_timestamp=\"784111777\""));
+    }
+
+    #[tokio::test]
+    async fn no_retry_with_pinned_timestamp() {
+        let (endpoint, server) = serve_dated(&["401 Unauthorized"]).await;
+
+        let skew = ClockSkew::with_clock(FixedClock(784_000_000));
+        let resp = ReqwestClient::builder()
+            .no_proxy()
+            .build()
+            .unwrap()
+            .oauth1(Secrets::new("consumer", "secret"))
+            .correct_clock_skew(skew.clone())
+            .get(&endpoint)
+            .oauth_timestamp(784_000_000u64)
+            .send()
+            .await
+            .unwrap();
+
+        // the same timestamp would be rejected again, but the offset is learned
+        assert_eq!(resp.status(), reqwest::StatusCode::UNAUTHORIZED);
+        assert_eq!(skew.offset(), 111_777);
+        assert_eq!(server.await.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn sign_extension_parameters() {
+        let endpoint = "http://photos.example.net/photos?file=vacation.jpg&size=original";
+        let secrets = Secrets::new("dpf43f3p2l4k3l03", "kd94hf93k423kf44")
+            .token("nnch734d00sl2jdk", "pfkkdhi9sl3r4s00");
+        let params = OAuthParameters::new()
+            .nonce("chapoH")
+            .timestamp(137_131_202u64)
+            .extension("oauth_session_handle", "ODU2NzE4&abc");
+
+        let req = ReqwestClient::new()
+            .oauth1_with_params(secrets, params)
+            .get(endpoint)
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let sign = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert!(sign.contains("oauth_session_handle=\"ODU2NzE4%26abc\""));
+        assert_eq!(extract_signature(sign), "K2sjsfwdgRO2kgQXlRg5XI+dviM=");
+    }
+
+    #[test]
+    fn capture_query_extension_parameters() {
+        let endpoint = "http://photos.example.net/photos?file=vacation.jpg&size=original";
+        let secrets = Secrets::new("dpf43f3p2l4k3l03", "kd94hf93k423kf44")
+            .token("nnch734d00sl2jdk", "pfkkdhi9sl3r4s00");
+        let params = OAuthParameters::new()
+            .nonce("chapoH")
+            .timestamp(137_131_202u64)
+            .extension("oauth_session_handle", "ThisHandleWillBeSuperseded");
+
+        let req = ReqwestClient::new()
+            .oauth1_with_params(secrets, params)
+            .get(endpoint)
+            .query(&[("oauth_session_handle", "ODU2NzE4&abc")])
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+
+        // captured parameters are moved into the Authorization header
+        assert_eq!(
+            req.url().as_str(),
+            "http://photos.example.net/photos?file=vacation.jpg&size=original"
+        );
+        let sign = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert!(sign.contains("oauth_session_handle=\"ODU2NzE4%26abc\""));
+        assert_eq!(extract_signature(sign), "K2sjsfwdgRO2kgQXlRg5XI+dviM=");
+    }
+
+    #[test]
+    fn refuse_reserved_extension_parameters() {
+        let params = OAuthParameters::new().extension("oauth_nonce", "nonce");
+        let result = ReqwestClient::new()
+            .oauth1_with_params(Secrets::new("consumer", "secret"), params)
+            .get("https://example.com/")
+            .generate_signature();
+        assert!(matches!(
+            result,
+            Err(SignerError::UnconfigurableParameter(key)) if key == "oauth_nonce"
+        ));
+    }
+
+    #[test]
+    fn sign_as_same_as_plain_signer() {
+        let endpoint = "https://example.com/photos?size=original&realm=Photos";
+        let params = || -> OAuthParameters<DefaultSM> {
+            OAuthParameters::new()
+                .nonce("nonce")
+                .timestamp(1_234_567_890u64)
+        };
+        let form = [("title", "vacation"), ("oauth_callback", "oob")];
+
+        let req = ReqwestClient::new()
+            .oauth1_with_params(Secrets::new("consumer", "secret"), params())
+            .post(endpoint)
+            .form(&form)
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+        let signed = Signer::new(Secrets::new("consumer", "secret"), params())
+            .sign("POST", endpoint, &form)
+            .unwrap();
+
+        assert_eq!(
+            req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap(),
+            signed.authorization_header()
+        );
+    }
+
+    #[test]
+    fn refuse_captured_body_hash() {
+        let result = ReqwestClient::new()
+            .oauth1(Secrets::new("consumer", "secret"))
+            .put("https://example.com/")
+            .query(&[("oauth_body_hash", "Lve95gjOVATpfV8EL5X4nxwjKHE=")])
+            .header(http::header::CONTENT_TYPE, "text/plain")
+            .body("Hello World!")
+            .generate_signature();
+        assert!(matches!(
+            result,
+            Err(SignerError::UnconfigurableParameter(key)) if key == "oauth_body_hash"
+        ));
+    }
+
+    #[cfg(feature = "multipart")]
+    #[test]
+    fn sign_multipart_text_parts() {
+        fn sign_with(builder: RequestBuilder<Signer<Secrets, DefaultSM>>) -> String {
+            let req = builder.generate_signature().unwrap().build().unwrap();
+            let sign = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+            extract_signature(sign)
+        }
+        let client = ReqwestClient::new().oauth1_with_params(
+            Secrets::new("consumer", "secret"),
+            OAuthParameters::new()
+                .nonce("nonce")
+                .timestamp(1_234_567_890u64),
+        );
+        let endpoint = "https://example.com/upload?a=b";
+
+        let form_signature = sign_with(client.post(endpoint).form(&[("title", "vacation")]));
+        let empty_signature = sign_with(client.post(endpoint));
+
+        // text parts are signed as the form
+        let signed = MultipartForm::new()
+            .text("title", "vacation")
+            .part("photo", multipart::Part::bytes(vec![0u8; 4]))
+            .sign_text_parts(true);
+        assert_eq!(
+            sign_with(client.post(endpoint).multipart(signed)),
+            form_signature
+        );
+
+        // not signed by default
+        let unsigned = MultipartForm::new().text("title", "vacation");
+        assert_eq!(
+            sign_with(client.post(endpoint).multipart(unsigned)),
+            empty_signature
+        );
+        let unsigned = multipart::Form::new().text("title", "vacation");
+        assert_eq!(
+            sign_with(client.post(endpoint).multipart(unsigned)),
+            empty_signature
+        );
+    }
+
+    #[cfg(feature = "multipart")]
+    #[test]
+    fn capture_multipart_oauth_params() {
+        fn sign_with(builder: RequestBuilder<Signer<Secrets, DefaultSM>>) -> String {
+            let req = builder.generate_signature().unwrap().build().unwrap();
+            let sign = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+            sign.to_string()
+        }
+        let client = ReqwestClient::new().oauth1_with_params(
+            Secrets::new("consumer", "secret"),
+            OAuthParameters::new()
+                .nonce("nonce")
+                .timestamp(1_234_567_890u64),
+        );
+        let endpoint = "https://example.com/upload";
+
+        let form_sign = sign_with(
+            client
+                .post(endpoint)
+                .form(&[("title", "vacation"), ("oauth_callback", "oob")]),
+        );
+        let signed = MultipartForm::new()
+            .text("title", "vacation")
+            .text("oauth_callback", "oob")
+            .sign_text_parts(true);
+        let sign = sign_with(client.post(endpoint).multipart(signed));
+        assert!(sign.contains("oauth_callback=\"oob\""));
+        assert_eq!(extract_signature(&sign), extract_signature(&form_sign));
+
+        // captured even if the text parts are not signed
+        let unsigned = MultipartForm::new()
+            .text("title", "vacation")
+            .text("oauth_callback", "oob");
+        let sign = sign_with(client.post(endpoint).multipart(unsigned));
+        assert!(sign.contains("oauth_callback=\"oob\""));
+        assert_eq!(
+            extract_signature(&sign),
+            extract_signature(&sign_with(client.post(endpoint).oauth_callback("oob")))
+        );
+    }
+
+    #[test]
+    fn transmit_with_query() {
+        // https://tools.ietf.org/html/rfc5849
+        let endpoint = "http://photos.example.net/photos?file=vacation.jpg&size=original";
+        let secrets = Secrets::new("dpf43f3p2l4k3l03", "kd94hf93k423kf44")
+            .token("nnch734d00sl2jdk", "pfkkdhi9sl3r4s00");
+        let params = OAuthParameters::new()
+            .nonce("chapoH")
+            .timestamp(137_131_202u64)
+            .realm("Photos")
+            .transmission(ParameterTransmission::QueryString);
+
+        let req = ReqwestClient::new()
+            .oauth1_with_params(secrets, params)
+            .get(endpoint)
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert!(req.headers().get(AUTHORIZATION).is_none());
+        let query: Vec<(String, String)> = req.url().query_pairs().into_owned().collect();
+        let get = |key: &str| {
+            query
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str())
+        };
+        assert_eq!(get("file"), Some("vacation.jpg"));
+        assert_eq!(get("oauth_consumer_key"), Some("dpf43f3p2l4k3l03"));
+        assert_eq!(get("oauth_token"), Some("nnch734d00sl2jdk"));
+        assert_eq!(get("oauth_signature"), Some("MdpQcU8iPSUjWoN/UDMsK2sui9I="));
+        // realm is only for the Authorization header
+        assert_eq!(get("realm"), None);
+    }
+
+    #[test]
+    fn transmit_with_form_body() {
+        // https://developer.twitter.com/ja/docs/basics/authentication/guides/creating-a-signature
+        let endpoint = "https://api.twitter.com/1.1/statuses/update.json";
+        let secrets = Secrets::new(
+            "xvz1evFS4wEEPTGEFPHBog",
+            "kAcSOqF21Fu85e7zjz7ZN2U4ZRhfV3WpwPAoE3Z7kBw",
+        )
+        .token(
+            "370773112-GmHxMAgYyLbNEtIKZeRNFsMKPR9EyMZeS9weJAEb",
+            "LswwdoUaIvS8ltyTt5jkRh4J50vUPVVHtR2YPi5kE",
+        );
+        let params = OAuthParameters::new()
+            .nonce("kYjzVBB8Y0ZFabxSWbWovY3uYSQ2pTgmZeNu2VS4cg")
+            .timestamp(1_318_622_958u64)
+            .version(true);
+
+        let req = ReqwestClient::new()
+            .oauth1_with_params(secrets, params)
+            .post(endpoint)
+            .form(&[
+                ("include_entities", "true"),
+                (
+                    "status",
+                    "Hello Ladies + Gentlemen, a signed OAuth request!",
+                ),
+            ])
+            .transmission(ParameterTransmission::FormBody)
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+
+        assert!(req.headers().get(AUTHORIZATION).is_none());
+        assert_eq!(
+            req.headers().get(http::header::CONTENT_TYPE).unwrap(),
+            "application/x-www-form-urlencoded"
+        );
+        let body = req.body().unwrap().as_bytes().unwrap();
+        assert!(body.starts_with(b"include_entities=true&status=Hello+Ladies"));
+        let form: Vec<(String, String)> = url::form_urlencoded::parse(body).into_owned().collect();
+        assert_eq!(form.len(), 9);
+        assert!(form.contains(&(
+            "oauth_signature".to_string(),
+            "hCtSmYh+iHYCEqBWrE7C7hYmtUk=".to_string()
+        )));
+    }
+
+    #[test]
+    fn override_transmission() {
+        let params = OAuthParameters::new().transmission(ParameterTransmission::QueryString);
+        let req = ReqwestClient::new()
+            .oauth1_with_params(Secrets::new("consumer", "secret"), params)
+            .get("https://example.com/")
+            .transmission(ParameterTransmission::AuthorizationHeader)
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(req.headers().get(AUTHORIZATION).is_some());
+        assert_eq!(req.url().query(), None);
+    }
+
+    #[test]
+    fn presign_url() {
+        // https://tools.ietf.org/html/rfc5849
+        let secrets = Secrets::new("dpf43f3p2l4k3l03", "kd94hf93k423kf44")
+            .token("nnch734d00sl2jdk", "pfkkdhi9sl3r4s00");
+        let params = OAuthParameters::new()
+            .nonce("chapoH")
+            .timestamp(137_131_202u64);
+
+        let url = ReqwestClient::new()
+            .oauth1_with_params(secrets, params)
+            .presigned_url("http://photos.example.net/photos?file=vacation.jpg&size=original")
+            .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "http://photos.example.net/photos?file=vacation.jpg&size=original\
+             &oauth_consumer_key=dpf43f3p2l4k3l03&oauth_nonce=chapoH\
+             &oauth_signature_method=HMAC-SHA1&oauth_timestamp=137131202\
+             &oauth_token=nnch734d00sl2jdk&oauth_signature=MdpQcU8iPSUjWoN%2FUDMsK2sui9I%3D"
+        );
+    }
+
+    #[test]
+    fn refuse_unpresignable_request() {
+        let secrets = Secrets::new("dpf43f3p2l4k3l03", "kd94hf93k423kf44");
+        let client = ReqwestClient::new().oauth1(secrets.clone());
+        let refused = |result: Result<url::Url, crate::Error>| {
+            matches!(
+                result,
+                Err(crate::Error::Signer(SignerError::UnpresignableRequest(_)))
+            )
+        };
+
+        assert!(refused(
+            client.post("https://example.com/photos").presigned_url()
+        ));
+        assert!(refused(
+            client
+                .get("https://example.com/photos")
+                .form(&[("file", "vacation.jpg")])
+                .presigned_url()
+        ));
+        assert!(refused(
+            client
+                .get("https://example.com/photos")
+                .body("Hello World!")
+                .presigned_url()
+        ));
+
+        // the signature of PLAINTEXT is the secrets
+        let params = OAuthParameters::new().signature_method(Plaintext);
+        assert!(refused(
+            ReqwestClient::new()
+                .oauth1_with_params(secrets, params)
+                .presigned_url("https://example.com/photos")
+        ));
+    }
+
+    #[test]
+    fn presign_url_with_query_oauth_params() {
+        // oauth_* parameters in the URL are captured and signed
+        let secrets = Secrets::new("dpf43f3p2l4k3l03", "kd94hf93k423kf44")
+            .token("nnch734d00sl2jdk", "pfkkdhi9sl3r4s00");
+        let url = ReqwestClient::new()
+            .oauth1(secrets)
+            .get("http://photos.example.net/photos?file=vacation.jpg&oauth_nonce=chapoH")
+            .query(&[("size", "original"), ("oauth_timestamp", "137131202")])
+            .presigned_url()
+            .unwrap();
+        let query: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+        assert_eq!(
+            query.iter().filter(|(k, _)| k == "oauth_nonce").count(),
+            1,
+            "{}",
+            url
+        );
+        assert!(query.contains(&(
+            "oauth_signature".to_string(),
+            "MdpQcU8iPSUjWoN/UDMsK2sui9I=".to_string()
+        )));
+    }
+
+    #[test]
+    fn form_transmission_requires_form_body() {
+        let result = ReqwestClient::new()
+            .oauth1(Secrets::new("consumer", "secret"))
+            .put("https://example.com/")
+            .body("Hello World!")
+            .transmission(ParameterTransmission::FormBody)
+            .generate_signature();
+        assert!(matches!(result, Err(SignerError::FormBodyUnavailable)));
+    }
+
+    #[test]
+    fn sign_urlencoded_raw_body() {
+        // https://developer.twitter.com/ja/docs/basics/authentication/guides/creating-a-signature
+        let endpoint = "https://api.twitter.com/1.1/statuses/update.json";
+        let secrets = Secrets::new(
+            "xvz1evFS4wEEPTGEFPHBog",
+            "kAcSOqF21Fu85e7zjz7ZN2U4ZRhfV3WpwPAoE3Z7kBw",
+        )
+        .token(
+            "370773112-GmHxMAgYyLbNEtIKZeRNFsMKPR9EyMZeS9weJAEb",
+            "LswwdoUaIvS8ltyTt5jkRh4J50vUPVVHtR2YPi5kE",
+        );
+        let params = OAuthParameters::new()
+            .nonce("kYjzVBB8Y0ZFabxSWbWovY3uYSQ2pTgmZeNu2VS4cg")
+            .timestamp(1_318_622_958u64)
+            .version(true);
+        let body = "include_entities=true&status=Hello%20Ladies%20%2B%20Gentlemen%2C%20a%20signed%20OAuth%20request%21";
+        let client = ReqwestClient::new().oauth1_with_params(secrets, params);
+
+        // with header
+        let req = client
+            .post(endpoint)
+            .header(
+                CONTENT_TYPE,
+                "application/x-www-form-urlencoded; charset=utf-8",
+            )
+            .body(body)
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+        let sign = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert!(!sign.contains("oauth_body_hash"));
+        assert_eq!(extract_signature(sign), "hCtSmYh+iHYCEqBWrE7C7hYmtUk=");
+
+        // with headers
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/x-www-form-urlencoded"),
+        );
+        let req = client
+            .post(endpoint)
+            .body(body)
+            .headers(headers)
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+        let sign = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert_eq!(extract_signature(sign), "hCtSmYh+iHYCEqBWrE7C7hYmtUk=");
+    }
+
+    #[test]
+    fn capture_urlencoded_raw_body_oauth_params() {
+        let client = ReqwestClient::new().oauth1_with_params(
+            Secrets::new("consumer", "secret"),
+            OAuthParameters::new()
+                .nonce("nonce")
+                .timestamp(1_234_567_890u64),
+        );
+        let endpoint = "https://example.com/initiate";
+
+        let expected = client
+            .post(endpoint)
+            .form(&[("a", "1"), ("oauth_callback", "x")])
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+        let expected = expected
+            .headers()
+            .get(AUTHORIZATION)
+            .unwrap()
+            .to_str()
+            .unwrap();
+
+        let req = client
+            .post(endpoint)
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body("a=1&oauth_callback=x")
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+        let sign = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert!(sign.contains("oauth_callback=\"x\""));
+        assert_eq!(extract_signature(sign), extract_signature(expected));
+        // the captured parameter is sent only in the Authorization header
+        assert_eq!(req.body().unwrap().as_bytes(), Some(&b"a=1"[..]));
+
+        // the parameters captured from the previous form are discarded
+        let req = client
+            .post(endpoint)
+            .form(&[("oauth_callback", "x")])
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body("a=1")
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+        let sign = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert!(!sign.contains("oauth_callback"));
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn refuse_stream_body() {
+        let stream = reqwest::blocking::Body::new(std::io::Cursor::new(b"Hello World!".to_vec()));
+        let result = reqwest::blocking::Client::new()
+            .oauth1(Secrets::new("consumer", "secret"))
+            .put("https://example.com/")
+            .body(stream)
+            .generate_signature();
+        assert!(matches!(result, Err(SignerError::UnreadableBody)));
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn sign_async_and_blocking_alike() {
+        let endpoint = "http://photos.example.net/photos?file=vacation.jpg&size=original";
+        let secrets = Secrets::new("dpf43f3p2l4k3l03", "kd94hf93k423kf44")
+            .token("nnch734d00sl2jdk", "pfkkdhi9sl3r4s00");
+        let params = OAuthParameters::new()
+            .nonce("chapoH")
+            .timestamp(137_131_202u64);
+
+        let async_signed = ReqwestClient::new()
+            .oauth1_with_params(secrets.clone(), params.clone())
+            .get(endpoint)
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+        let blocking_signed = reqwest::blocking::Client::new()
+            .oauth1_with_params(secrets, params)
+            .get(endpoint)
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(
+            async_signed.headers().get(AUTHORIZATION),
+            blocking_signed.headers().get(AUTHORIZATION)
+        );
+    }
+
+    #[test]
+    fn report_signing() {
+        // https://tools.ietf.org/html/rfc5849
+        let endpoint = "http://photos.example.net/photos?file=vacation.jpg&size=original";
+        let secrets = Secrets::new("dpf43f3p2l4k3l03", "kd94hf93k423kf44")
+            .token("nnch734d00sl2jdk", "pfkkdhi9sl3r4s00");
+        let params = OAuthParameters::new()
+            .nonce("chapoH")
+            .timestamp(137_131_202u64)
+            .realm("Photos");
+
+        let builder = ReqwestClient::new()
+            .oauth1_with_params(secrets, params)
+            .get(endpoint);
+        let report = builder.signing_report().unwrap();
+
+        assert_eq!(report.method, "GET");
+        assert_eq!(report.normalized_url, "http://photos.example.net/photos");
+        let keys: Vec<&str> = report.parameters.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(
+            keys,
+            [
+                "file",
+                "oauth_consumer_key",
+                "oauth_nonce",
+                "oauth_signature_method",
+                "oauth_timestamp",
+                "oauth_token",
+                "size"
+            ]
+        );
+        assert_eq!(
+            report.base_string,
+            "GET&http%3A%2F%2Fphotos.example.net%2Fphotos&file%3Dvacation.jpg%26\
+             oauth_consumer_key%3Ddpf43f3p2l4k3l03%26oauth_nonce%3DchapoH%26\
+             oauth_signature_method%3DHMAC-SHA1%26oauth_timestamp%3D137131202%26\
+             oauth_token%3Dnnch734d00sl2jdk%26size%3Doriginal"
+        );
+        assert_eq!(report.signing_key, "[REDACTED]&[REDACTED]");
+        assert!(!report.to_string().contains("kd94hf93k423kf44"));
+        assert_eq!(
+            extract_signature(&report.authorization_header),
+            "MdpQcU8iPSUjWoN/UDMsK2sui9I="
+        );
+
+        // the builder is still available
+        let req = builder.generate_signature().unwrap().build().unwrap();
+        let sign = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert_eq!(sign, report.authorization_header);
+    }
+
+    #[test]
+    fn sign_get_query_hmac_sha2() {
+        // the request of https://tools.ietf.org/html/rfc5849#section-3.4.1.1
+        // expected values are computed with Python's hmac module
+        let endpoint = "http://photos.example.net/photos?file=vacation.jpg&size=original";
+        let secrets = Secrets::new("dpf43f3p2l4k3l03", "kd94hf93k423kf44")
+            .token("nnch734d00sl2jdk", "pfkkdhi9sl3r4s00");
+
+        let params = OAuthParameters::new()
+            .signature_method(HmacSha256)
+            .nonce("chapoH")
+            .timestamp(137_131_202u64);
+        let req = ReqwestClient::new()
+            .oauth1_with_params(secrets.clone(), params)
+            .get(endpoint)
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+        let sign = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert!(sign.contains("oauth_signature_method=\"HMAC-SHA256\""));
+        assert_eq!(
+            extract_signature(sign),
+            "HtMwoX2zenlFjgGg/SNEoKEQmL7CzxYFEKzs7er044Y="
+        );
+
+        let params = OAuthParameters::new()
+            .signature_method(HmacSha512)
+            .nonce("chapoH")
+            .timestamp(137_131_202u64);
+        let req = ReqwestClient::new()
+            .oauth1_with_params(secrets, params)
+            .get(endpoint)
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+        let sign = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert!(sign.contains("oauth_signature_method=\"HMAC-SHA512\""));
+        assert_eq!(
+            extract_signature(sign),
+            "GnPni/I//SEqvsTDz9Hl/oqxAlzMUgeQVrspr+N1EWltelChqWWuhrgewHZy90k8K2weeJkkURa/W10NRXY7uQ=="
+        );
+    }
+
+    #[test]
+    fn sign_body_hash() {
+        // the request of the OAuth Request Body Hash specification
+        // expected signature is computed with Python's hmac module
+        let secrets = Secrets::new("consumer", "secret");
+        let params = OAuthParameters::new()
+            .nonce("10369470270925")
+            .timestamp(1_236_874_236u64)
+            .version(true);
+        let req = ReqwestClient::new()
+            .oauth1_with_params(secrets, params)
+            .put("http://www.example.com/resource")
+            .header(http::header::CONTENT_TYPE, "text/plain")
+            .body("Hello World!")
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+        let sign = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert!(sign.starts_with("OAuth oauth_body_hash=\"Lve95gjOVATpfV8EL5X4nxwjKHE%3D\","));
+        assert_eq!(extract_signature(sign), "uKE5cKGgrQlrowG4Hw/9tgi+eiY=");
+    }
+
+    #[test]
+    fn verify_specified_body_hash() {
+        let client = ReqwestClient::new();
+        let sign_with = |body_hash: &str| {
+            let params = OAuthParameters::new().extension("oauth_body_hash", body_hash.to_string());
+            client
+                .clone()
+                .oauth1_with_params(Secrets::new("consumer", "secret"), params)
+                .put("http://www.example.com/resource")
+                .header(http::header::CONTENT_TYPE, "text/plain")
+                .body("Hello World!")
+                .generate_signature()
+        };
+
+        let req = sign_with("Lve95gjOVATpfV8EL5X4nxwjKHE=")
+            .unwrap()
+            .build()
+            .unwrap();
+        let sign = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert_eq!(sign.matches("oauth_body_hash=").count(), 1);
+
+        // the stale hash is never signed
+        let result = sign_with("2jmj7l5rSw0yVb/vlWAYkK/YBwk=");
+        assert!(matches!(
+            result,
+            Err(SignerError::UnconfigurableParameter(key)) if key == "oauth_body_hash"
+        ));
+    }
+
+    #[test]
+    fn form_has_no_body_hash() {
+        let secrets = Secrets::new("consumer", "secret");
+        let req = ReqwestClient::new()
+            .oauth1(secrets)
+            .post("http://www.example.com/resource")
+            .body("ignored")
+            .form(&[("status", "Hello World!")])
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+        let sign = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert!(!sign.contains("oauth_body_hash"));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn sign_json_body_hash() {
+        let secrets = Secrets::new("consumer", "secret");
+        let req = ReqwestClient::new()
+            .oauth1_with_params(secrets, OAuthParameters::new().signature_method(HmacSha256))
+            .post("http://www.example.com/resource")
+            .json(&[("status", "Hello World!")])
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+        let sign = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+        // SHA-256 of `[["status","Hello World!"]]`
+        assert!(
+            sign.contains("oauth_body_hash=\"kDFesJC%2BAAfcCP%2Fpq0anRTjY3H2kN7yGGT1HHJZF9AU%3D\"")
+        );
+    }
+
+    #[tokio::test]
+    async fn sign_get_query_with_backend() {
+        // https://tools.ietf.org/html/rfc5849
+        let endpoint = "http://photos.example.net/photos?file=vacation.jpg&size=original";
+        let c_key = "dpf43f3p2l4k3l03";
+        let c_secret = "kd94hf93k423kf44";
+        let token = "nnch734d00sl2jdk";
+        let token_secret = "pfkkdhi9sl3r4s00";
+
+        // the backend holds the secrets, so they are not passed to the client
+        let secrets = Secrets::new(c_key, "").token(token, "");
+        let params = OAuthParameters::new()
+            .nonce("chapoH")
+            .timestamp(137_131_202u64)
+            .realm("Photos");
+        let backend = LocalSigningBackend::hmac_sha1(c_secret, Some(token_secret));
+
+        let req = ReqwestClient::new()
+            .oauth1_with_backend(secrets, params, backend)
+            .get(endpoint)
+            .generate_signature()
+            .await
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let sign = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert!(sign.ends_with(",realm=\"Photos\""));
+        assert_eq!(extract_signature(sign), "MdpQcU8iPSUjWoN/UDMsK2sui9I=");
+    }
+
+    #[tokio::test]
+    async fn backend_failure() {
+        struct FailingBackend;
+
+        #[async_trait::async_trait]
+        impl SigningBackend for FailingBackend {
+            fn signature_method_name(&self) -> &'static str {
+                "RSA-SHA1"
+            }
+
+            async fn sign(&self, _base_string: &str) -> Result<Vec<u8>, BackendError> {
+                Err("signing daemon is unavailable".into())
+            }
+        }
+
+        let result = Client::new()
+            .get("https://example.com/")
+            .sign_with_backend(
+                Secrets::new("dpf43f3p2l4k3l03", ""),
+                OAuthParameters::new(),
+                FailingBackend,
+            )
+            .generate_signature()
+            .await;
+        assert!(matches!(result, Err(SignerError::Backend(_))));
+    }
+
+    #[test]
+    fn sign_plaintext_over_https() {
+        let endpoint = "https://photos.example.net/photos?file=vacation.jpg&size=original";
+        let secrets = Secrets::new("dpf43f3p2l4k3l03", "kd94hf93k423kf44")
+            .token("nnch734d00sl2jdk", "pfkkdhi9sl3r4s00");
+        let params = OAuthParameters::new().signature_method(Plaintext);
+
+        let req = ReqwestClient::new()
+            .oauth1_with_params(secrets, params)
+            .get(endpoint)
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let sign = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert!(sign.contains("oauth_signature_method=\"PLAINTEXT\""));
+        assert_eq!(extract_signature(sign), "kd94hf93k423kf44&pfkkdhi9sl3r4s00");
+    }
+
+    #[test]
+    fn refuse_plaintext_over_http() {
+        let endpoint = "http://photos.example.net/photos?file=vacation.jpg&size=original";
+        let secrets = Secrets::new("dpf43f3p2l4k3l03", "kd94hf93k423kf44");
+        let params = OAuthParameters::new().signature_method(Plaintext);
+
+        let result = ReqwestClient::new()
+            .oauth1_with_params(secrets, params)
+            .get(endpoint)
+            .generate_signature();
+        assert!(matches!(
+            result,
+            Err(SignerError::InsecureTransport(origin)) if origin == "http://photos.example.net"
+        ));
+    }
+
+    #[test]
+    fn allow_plaintext_over_http() {
+        let endpoint = "http://photos.example.net/photos?file=vacation.jpg&size=original";
+        let secrets = Secrets::new("dpf43f3p2l4k3l03", "kd94hf93k423kf44");
+        let params = OAuthParameters::new()
+            .signature_method(Plaintext)
+            .allow_insecure_plaintext(true);
+
+        let req = ReqwestClient::new()
+            .oauth1_with_params(secrets, params)
+            .get(endpoint)
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let sign = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert_eq!(extract_signature(sign), "kd94hf93k423kf44&");
+    }
+
+    #[test]
+    fn strict_refuses_invalid_url() {
+        let client = ReqwestClient::new();
+        let lenient = client
+            .clone()
+            .oauth1(Secrets::new("consumer", "secret"))
+            .get("not a url")
+            .generate_signature();
+        assert!(lenient.is_ok());
+
+        let strict = client
+            .oauth1_with_params(
+                Secrets::new("consumer", "secret"),
+                OAuthParameters::new().strict(true),
+            )
+            .get("not a url")
+            .generate_signature();
+        assert!(matches!(strict, Err(SignerError::InvalidUrl)));
+    }
+
+    #[test]
+    fn strict_refuses_uncaptured_parameters() {
+        let client = ReqwestClient::new().oauth1_with_params(
+            Secrets::new("consumer", "secret"),
+            OAuthParameters::new().strict(true),
+        );
+
+        let result = client
+            .get("https://example.com/")
+            .query_without_capture(&[("oauth_session_handle", "handle")])
+            .generate_signature();
+        assert!(
+            matches!(result, Err(SignerError::UnsignedParameter(key)) if key == "oauth_session_handle")
+        );
+        let result = client
+            .post("https://example.com/")
+            .form_without_capture(&[("realm", "Photos")])
+            .generate_signature();
+        assert!(matches!(result, Err(SignerError::UnsignedParameter(key)) if key == "realm"));
+
+        // other parameters are sent as same as the lenient mode
+        let result = client
+            .get("https://example.com/")
+            .query_without_capture(&[("file", "vacation.jpg")])
+            .generate_signature();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn strict_refuses_unhashed_body() {
+        let params = OAuthParameters::new().signature_method(Plaintext);
+        let client = ReqwestClient::new();
+
+        // PLAINTEXT does not sign the oauth_body_hash
+        let lenient = client
+            .clone()
+            .oauth1_with_params(Secrets::new("consumer", "secret"), params.clone())
+            .put("https://example.com/")
+            .body("Hello World!")
+            .generate_signature();
+        assert!(lenient.is_ok());
+
+        let strict = client
+            .oauth1_with_params(Secrets::new("consumer", "secret"), params.strict(true))
+            .put("https://example.com/")
+            .body("Hello World!")
+            .generate_signature();
+        assert!(matches!(strict, Err(SignerError::UnsignedBody)));
+    }
+
+    #[test]
+    fn discard_form_failed_to_serialize() {
+        let client = ReqwestClient::new().oauth1_with_params(
+            Secrets::new("consumer", "secret"),
+            OAuthParameters::new()
+                .nonce("nonce")
+                .timestamp(1_234_567_890u64),
+        );
+        let builder = client
+            .post("https://example.com/")
+            .form(&[("title", "vacation")])
+            // nested sequences could not be serialized
+            .form(&[("size", [1, 2])]);
+
+        // the previous form is not signed
+        let report = builder.signing_report().unwrap();
+        assert!(!report.base_string.contains("title"));
+
+        let strict = ReqwestClient::new()
+            .oauth1_with_params(
+                Secrets::new("consumer", "secret"),
+                OAuthParameters::new().strict(true),
+            )
+            .post("https://example.com/")
+            .form(&[("size", [1, 2])])
+            .generate_signature();
+        assert!(matches!(strict, Err(SignerError::UnsignedBody)));
+    }
+
+    #[cfg(feature = "multipart")]
+    #[test]
+    fn strict_refuses_unsigned_multipart() {
+        let client = ReqwestClient::new().oauth1_with_params(
+            Secrets::new("consumer", "secret"),
+            OAuthParameters::new().strict(true),
+        );
+
+        let unsigned = MultipartForm::new().text("title", "vacation");
+        let result = client
+            .post("https://example.com/upload")
+            .multipart(unsigned)
+            .generate_signature();
+        assert!(matches!(result, Err(SignerError::UnsignedBody)));
+
+        let signed = MultipartForm::new()
+            .text("title", "vacation")
+            .sign_text_parts(true);
+        let result = client
+            .post("https://example.com/upload")
+            .multipart(signed)
+            .generate_signature();
+        assert!(result.is_ok());
+
+        // the file parts are never signed
+        let with_file = MultipartForm::new()
+            .text("title", "vacation")
+            .part("photo", multipart::Part::bytes(vec![0u8; 4]))
+            .sign_text_parts(true);
+        let result = client
+            .post("https://example.com/upload")
+            .multipart(with_file)
+            .generate_signature();
+        assert!(matches!(result, Err(SignerError::UnsignedBody)));
+
+        // the parts of the wrapped form are unknown
+        let wrapped = MultipartForm::from(multipart::Form::new().text("title", "vacation"))
+            .sign_text_parts(true);
+        let result = client
+            .post("https://example.com/upload")
+            .multipart(wrapped)
+            .generate_signature();
+        assert!(matches!(result, Err(SignerError::UnsignedBody)));
+    }
+
+    #[cfg(feature = "rsa-sha1")]
+    #[test]
+    fn sign_get_query_rsa_sha1() {
+        // the request of http://wiki.oauth.net/TestCases, signed with the key
+        // generated for the tests of rsa_sha1.rs instead of the published one
+        let endpoint = "http://photos.example.net/photos?file=vacaction.jpg&size=original";
+        let c_key = "dpf43f3p2l4k3l03";
+        let nonce = "13917289812797014437";
+        let timestamp = 1_196_666_512u64;
+
+        let secrets = Secrets::new(c_key, "");
+        let params = OAuthParameters::new()
+            .signature_method(RsaSha1::from_pem(PKCS8_PEM).unwrap())
+            .nonce(nonce)
+            .timestamp(timestamp)
+            .version(true);
+
+        let req = ReqwestClient::new()
+            .oauth1_with_params(secrets, params)
+            .get(endpoint)
+            .generate_signature()
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let sign = req.headers().get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert!(sign.contains("oauth_signature_method=\"RSA-SHA1\""));
+        assert_eq!(extract_signature(sign), EXPECTED_SIGNATURE);
     }
 }
- 
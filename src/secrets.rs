@@ -1,12 +1,12 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
 
 /// Interface of OAuth secrets provider
 pub trait SecretsProvider {
-    fn get_consumer_key_pair<'a>(&'a self) -> (&'a str, &'a str);
+    fn get_consumer_key_pair(&self) -> (&str, &str);
 
-    fn get_token_pair_option<'a>(&'a self) -> Option<(&'a str, &'a str)>;
+    fn get_token_pair_option(&self) -> Option<(&str, &str)>;
 
-    fn get_token_option_pair<'a>(&'a self) -> (Option<&'a str>, Option<&'a str>) {
+    fn get_token_option_pair(&self) -> (Option<&str>, Option<&str>) {
         self.get_token_pair_option()
             .map(|s| (Some(s.0), Some(s.1)))
             .unwrap_or_else(|| (None, None))
@@ -22,10 +22,6 @@ The token and token_secret are optional.
 ```rust
 use reqwest_oauth1::OAuthClientProvider;
 
-#[cfg(feature = "blocking")]
-use reqwest::blocking::Client as Client;
-
-#[cfg(not(feature = "blocking"))]
 use reqwest::Client;
 
 let consumer_key = "[CONSUMER_KEY]";
@@ -95,14 +91,13 @@ impl<'a> Secrets<'a> {
 }
 
 impl SecretsProvider for Secrets<'_> {
-    fn get_consumer_key_pair<'a>(&'a self) -> (&'a str, &'a str) {
+    fn get_consumer_key_pair(&self) -> (&str, &str) {
         (&self.consumer_key_secret.0, &self.consumer_key_secret.1)
     }
 
-    fn get_token_pair_option<'a>(&'a self) -> Option<(&'a str, &'a str)> {
-        match &self.token_key_secret {
-            Some((k, s)) => Some((&k, &s)),
-            None => None,
-        }
+    fn get_token_pair_option(&self) -> Option<(&str, &str)> {
+        self.token_key_secret
+            .as_ref()
+            .map(|(k, s)| (k.as_ref(), s.as_ref()))
     }
 }
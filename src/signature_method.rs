@@ -0,0 +1,139 @@
+use core::fmt::Display;
+
+use crate::SignResult;
+
+/**
+Types that represent a signature method, e.g. `HMAC-SHA1`.
+
+The interface is compatible with the `SignatureMethod` trait of
+[oauth1-request](https://crates.io/crates/oauth1-request) 0.3,
+so the existing implementations can be migrated by replacing the imports.
+
+# Note
+
+The signer passes the secrets and the parameters to the implementations
+already percent-encoded as described in the RFC 5849 section 3.6.
+*/
+pub trait SignatureMethod {
+    /// The algorithm used to sign the signature base string.
+    type Sign: Sign;
+
+    /// Create a `Sign` with the (percent-encoded) consumer secret and token secret.
+    fn sign_with(
+        self,
+        consumer_secret: impl Display,
+        token_secret: Option<impl Display>,
+    ) -> Self::Sign;
+}
+
+/**
+Algorithms to sign the signature base string.
+
+The signer feeds the parts of the signature base string in the following order:
+`request_method`, `uri`, and the parameters sorted as described in the
+RFC 5849 section 3.4.1.3.2, delimited with `delimiter`.
+
+cf. https://tools.ietf.org/html/rfc5849#section-3.4.1
+*/
+pub trait Sign {
+    /// The signature produced by the algorithm. It is displayed percent-encoded.
+    type Signature: Display;
+
+    /// Returns the `oauth_signature_method` value.
+    fn get_signature_method_name(&self) -> &'static str;
+
+    /// Feeds the HTTP request method part of the signature base string.
+    fn request_method(&mut self, method: &str);
+
+    /// Feeds the (percent-encoded) base string URI part of the signature base string.
+    fn uri(&mut self, uri: impl Display);
+
+    /// Feeds the parameter part of the signature base string.
+    ///
+    /// Both of the `key` and the `value` are already percent-encoded twice.
+    fn parameter(&mut self, key: &str, value: impl Display);
+
+    /// Feeds the delimiter (`%26`) between the parameters.
+    fn delimiter(&mut self);
+
+    /// Finish the signing and returns the signature.
+    fn finish(self) -> Self::Signature;
+
+    /// Finish the signing, or returns the error when the algorithm failed
+    /// to sign (e.g. with the private key).
+    ///
+    /// The signer calls this method. The default implementation forwards
+    /// to the `finish` method.
+    fn try_finish(self) -> SignResult<Self::Signature>
+    where
+        Self: Sized,
+    {
+        Ok(self.finish())
+    }
+
+    /// Feeds the `oauth_callback` parameter.
+    ///
+    /// The default implementation forwards to the `parameter` method.
+    fn callback(&mut self, default_key: &'static str, value: impl Display) {
+        self.parameter(default_key, value);
+    }
+
+    /// Feeds the `oauth_consumer_key` parameter.
+    ///
+    /// The default implementation forwards to the `parameter` method.
+    fn consumer_key(&mut self, default_key: &'static str, value: impl Display) {
+        self.parameter(default_key, value);
+    }
+
+    /// Feeds the `oauth_nonce` parameter.
+    ///
+    /// The default implementation forwards to the `parameter` method.
+    fn nonce(&mut self, default_key: &'static str, value: impl Display) {
+        self.parameter(default_key, value);
+    }
+
+    /// Whether the signature method uses the `oauth_nonce` parameter (default: `true`).
+    fn use_nonce(&self) -> bool {
+        true
+    }
+
+    /// Feeds the `oauth_signature_method` parameter.
+    ///
+    /// The default implementation forwards to the `parameter` method.
+    fn signature_method(&mut self, default_key: &'static str, default_value: &'static str) {
+        self.parameter(default_key, default_value);
+    }
+
+    /// Feeds the `oauth_timestamp` parameter.
+    ///
+    /// The default implementation forwards to the `parameter` method.
+    fn timestamp(&mut self, default_key: &'static str, value: u64) {
+        self.parameter(default_key, value);
+    }
+
+    /// Whether the signature method uses the `oauth_timestamp` parameter (default: `true`).
+    fn use_timestamp(&self) -> bool {
+        true
+    }
+
+    /// Feeds the `oauth_token` parameter.
+    ///
+    /// The default implementation forwards to the `parameter` method.
+    fn token(&mut self, default_key: &'static str, value: impl Display) {
+        self.parameter(default_key, value);
+    }
+
+    /// Feeds the `oauth_verifier` parameter.
+    ///
+    /// The default implementation forwards to the `parameter` method.
+    fn verifier(&mut self, default_key: &'static str, value: impl Display) {
+        self.parameter(default_key, value);
+    }
+
+    /// Feeds the `oauth_version` parameter.
+    ///
+    /// The default implementation forwards to the `parameter` method.
+    fn version(&mut self, default_key: &'static str, default_value: &'static str) {
+        self.parameter(default_key, default_value);
+    }
+}
@@ -1,26 +1,55 @@
-use std::{borrow::Cow, collections::HashMap};
+use alloc::{
+    borrow::Cow,
+    collections::BTreeMap,
+    format,
+    rc::Rc,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use core::{
+    cell::RefCell,
+    fmt::{Display, Write},
+};
 
-use crate::{SecretsProvider, SignResult, SignerError};
+use crate::body_hash::body_hash;
+use crate::plaintext::PLAINTEXT_METHOD_NAME;
+use crate::report::SigningReport;
+use crate::util::{base_string_uri, percent_encode, url_origin};
 use crate::{
-    OAUTH_CALLBACK_KEY, OAUTH_CONSUMER_KEY, OAUTH_KEY_PREFIX, OAUTH_NONCE_KEY,
-    OAUTH_SIGNATURE_METHOD_KEY, OAUTH_TIMESTAMP_KEY, OAUTH_TOKEN_KEY, OAUTH_VERIFIER_KEY,
-    OAUTH_VERSION_KEY, REALM_KEY,
+    Clock, HmacSha1, NonceGenerator, SecretsProvider, Sign, SignResult, SignatureMethod,
+    SignerError,
 };
-use http::Method;
-use oauth1_request::signature_method::SignatureMethod;
-use oauth1_request::signer::Signer as OAuthSigner;
-use oauth1_request::{HmacSha1, Options};
-use url::Url;
+use crate::{
+    OAUTH_BODY_HASH_KEY, OAUTH_CALLBACK_KEY, OAUTH_CONSUMER_KEY, OAUTH_KEY_PREFIX, OAUTH_NONCE_KEY,
+    OAUTH_SIGNATURE_KEY, OAUTH_SIGNATURE_METHOD_KEY, OAUTH_TIMESTAMP_KEY, OAUTH_TOKEN_KEY,
+    OAUTH_VERIFIER_KEY, OAUTH_VERSION_KEY, OAUTH_VERSION_VALUE, REALM_KEY,
+};
+use percent_encoding::percent_decode_str;
 
 /**
-Provides OAuth signature with [oauth1-request](https://crates.io/crates/oauth1-request).
+Provides OAuth signature as described in [RFC 5849](https://tools.ietf.org/html/rfc5849).
 
-# Note
+The reqwest layer uses this struct internally.
+You can also sign the request from the plain method, URL, and parameters,
+without the `reqwest` feature (and without `std`).
 
-This struct is intended for internal use.
+# Basic usage
+
+```rust
+use reqwest_oauth1::*;
 
-You may consider use the struct provided from oauth1-request crate directly
-instead of this struct.
+let secrets = Secrets::new("[CONSUMER_KEY]", "[CONSUMER_SECRET]");
+// without the std feature, the nonce and the timestamp must be specified
+let params = OAuthParameters::new()
+    .nonce("[NONCE]")
+    .timestamp(100_000_001u64);
+
+let signed = Signer::new(secrets, params)
+    .sign("POST", "https://example.com/photos?size=original", &[("title", "vacation")])
+    .unwrap();
+let authorization = signed.authorization_header();
+```
 
 */
 #[derive(Debug, Clone)]
@@ -45,7 +74,10 @@ where
         }
     }
 
-    pub fn override_oauth_parameter(mut self, parameters: HashMap<String, String>) -> Self {
+    pub fn override_oauth_parameter<I>(mut self, parameters: I) -> Self
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
         for (key, value) in parameters {
             self.parameters = match self.parameters {
                 Ok(p) => match key.as_str() {
@@ -65,10 +97,13 @@ where
                         _ => Err(SignerError::InvalidVersion(value)),
                     },
                     // always fail
-                    OAUTH_SIGNATURE_METHOD_KEY | OAUTH_CONSUMER_KEY | OAUTH_TOKEN_KEY => {
-                        Err(SignerError::UnconfigurableParameter(key))
-                    }
-                    _ => Err(SignerError::UnknownParameter(key)),
+                    OAUTH_SIGNATURE_METHOD_KEY
+                    | OAUTH_CONSUMER_KEY
+                    | OAUTH_TOKEN_KEY
+                    | OAUTH_SIGNATURE_KEY
+                    | OAUTH_BODY_HASH_KEY => Err(SignerError::UnconfigurableParameter(key)),
+                    // extension parameters
+                    _ => Ok(p.extension(key, value)),
                 },
                 Err(e) => Err(e),
             };
@@ -77,109 +112,487 @@ where
         self
     }
 
-    /// Generate OAuth signature with specified parameters.
-    pub(crate) fn generate_signature(
+    /// Replace the clock to generate the oauth_timestamp value.
+    #[cfg(feature = "reqwest")]
+    pub(crate) fn clock<T>(self, clock: T) -> Self
+    where
+        T: Clock + 'static,
+    {
+        Signer {
+            parameters: self.parameters.map(|p| p.clock(clock)),
+            ..self
+        }
+    }
+
+    /// Modify the OAuth parameters, e.g. for each request.
+    #[cfg(feature = "reqwest")]
+    pub(crate) fn map_parameters<F>(self, f: F) -> Self
+    where
+        F: FnOnce(OAuthParameters<'a, TSM>) -> OAuthParameters<'a, TSM>,
+    {
+        Signer {
+            parameters: self.parameters.map(f),
+            ..self
+        }
+    }
+
+    /// Whether the signer refuses to sign the request partially.
+    #[cfg(feature = "reqwest")]
+    pub(crate) fn is_strict(&self) -> bool {
+        self.parameters.as_ref().map_or(false, |p| p.strict)
+    }
+
+    /// The `oauth_signature_method` value, or `None` when the parameters are invalid.
+    #[cfg(feature = "reqwest")]
+    pub(crate) fn signature_method_name(&self) -> Option<&'static str> {
+        self.parameters
+            .as_ref()
+            .ok()
+            .map(|p| p.signature_method_name())
+    }
+
+    /// Whether the oauth_timestamp or the oauth_nonce is fixed,
+    /// so the request could not be signed again with the new ones.
+    #[cfg(feature = "reqwest")]
+    pub(crate) fn is_pinned(&self) -> bool {
+        self.parameters
+            .as_ref()
+            .map_or(false, |p| p.timestamp.is_some() || p.nonce.is_some())
+    }
+
+    /// Sign the request with the plain method, URL, and parameters.
+    ///
+    /// The `parameters` are the (decoded) form parameters of the request.
+    /// The query of the `url` is signed together, and the `oauth_*` (and `realm`)
+    /// parameters in them supersede the `OAuthParameters` as same as the reqwest layer.
+    ///
+    /// This method is available without the `reqwest` feature.
+    pub fn sign<K, V>(
+        self,
+        method: &str,
+        url: &str,
+        parameters: &[(K, V)],
+    ) -> SignResult<SignedParameters>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.sign_request(method, url, parameters, None)
+    }
+
+    /// Sign the request with the plain method, URL, parameters, and the
+    /// non-form body, whose `oauth_body_hash` is signed together.
+    pub fn sign_with_body<K, V>(
+        self,
+        method: &str,
+        url: &str,
+        parameters: &[(K, V)],
+        body: &[u8],
+    ) -> SignResult<SignedParameters>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        self.sign_request(method, url, parameters, Some(body))
+    }
+
+    fn sign_request<K, V>(
+        self,
+        method: &str,
+        url: &str,
+        parameters: &[(K, V)],
+        body: Option<&[u8]>,
+    ) -> SignResult<SignedParameters>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let query = match url.split_once('?') {
+            Some((_, query)) => query.split('#').next().unwrap_or_default(),
+            None => "",
+        };
+        let mut oauth_parameters = Vec::new();
+        let mut payload = form_urlencoded::Serializer::new(String::new());
+        let query = form_urlencoded::parse(query.as_bytes());
+        let parameters = parameters
+            .iter()
+            .map(|(k, v)| (Cow::from(k.as_ref()), Cow::from(v.as_ref())));
+        for (key, value) in query.chain(parameters) {
+            if key.starts_with(OAUTH_KEY_PREFIX) || key == REALM_KEY {
+                oauth_parameters.push((key.into_owned(), value.into_owned()));
+            } else {
+                payload.append_pair(&key, &value);
+            }
+        }
+        self.override_oauth_parameter(oauth_parameters)
+            .sign_parameters(method, url, &payload.finish(), body)
+    }
+
+    /// Generate OAuth signature and collect the signed `oauth_*` parameters.
+    ///
+    /// When the non-form `body` is specified, its `oauth_body_hash` is signed together.
+    pub(crate) fn sign_parameters(
         self,
-        method: Method,
-        url: Url,
+        method: &str,
+        url: &str,
         payload: &str,
-        is_url_query: bool,
-    ) -> SignResult<String> {
+        body: Option<&[u8]>,
+    ) -> SignResult<SignedParameters> {
         let (consumer_key, consumer_secret) = self.secrets.get_consumer_key_pair();
         let (token, token_secret) = self.secrets.get_token_option_pair();
-        // build oauth option
         let params = self.parameters?;
-        let options = params.build_options(token);
-
-        // destructure query and sort by alphabetical order
-        let parsed_payload: Vec<(Cow<str>, Cow<str>)> =
-            url::form_urlencoded::parse(payload.as_bytes())
-                .into_iter()
-                .collect();
-        // add `oauth_` key to identify where to divide
-        let oauth_identifier = vec![(Cow::from(OAUTH_KEY_PREFIX), Cow::from(""))];
-        let mut sorted_query = [parsed_payload, oauth_identifier].concat();
-
-        // then, sort by alphabetical order (that is required by OAuth specification)
-        sorted_query.sort();
-
-        // divide key-value items by the element has "oauth_" key
-        let mut divided = sorted_query
-            .splitn(2, |(k, _)| k == &OAUTH_KEY_PREFIX)
-            .into_iter();
-        let query_before_oauth = divided.next().unwrap();
-        let query_after_oauth = divided.next().unwrap_or_default();
+        params.check_transport(url)?;
+        params.check_extensions()?;
 
         // generate signature
         // Step 0. instantiate sign generator
-        let sig_method = params.signature_method.clone();
-        // println!("signing url: {:#?}", url);
-        let mut signer = generate_signer(
-            sig_method,
-            method.as_str(),
-            url,
-            consumer_secret,
-            token_secret,
-            is_url_query,
+        let base_string = Rc::new(RefCell::new(String::new()));
+        let sig_method = Capture {
+            inner: params.signature_method.clone(),
+            base_string: base_string.clone(),
+        };
+        let mut sign = sig_method.sign_with(
+            percent_encode(consumer_secret),
+            token_secret.map(percent_encode),
         );
+        // cf. https://tools.ietf.org/html/rfc5849#section-3.4.1.2
+        sign.request_method(&method.to_ascii_uppercase());
+        sign.uri(percent_encode(&base_string_uri(url)?));
 
-        // Step 1. key [a ~ oauth_)
-        for (key, value) in query_before_oauth {
-            if !key.starts_with(OAUTH_KEY_PREFIX) {
-                // not an oauth_* parameter
-                signer.parameter(key, value);
+        // Step 1. collect oauth_* parameters
+        let mut items = params.oauth_items(&sign, consumer_key, token)?;
+        // the oauth_body_hash is always computed over the actual body,
+        // and the specified one must be the same
+        items.retain(|(key, _, _)| key != OAUTH_BODY_HASH_KEY);
+        let body_hash = body.and_then(|body| body_hash(sign.get_signature_method_name(), body));
+        if let Some(specified) = params.extensions.get(OAUTH_BODY_HASH_KEY) {
+            if body_hash.as_deref() != Some(specified.as_ref()) {
+                return Err(SignerError::UnconfigurableParameter(
+                    OAUTH_BODY_HASH_KEY.to_string(),
+                ));
             }
         }
-        // Step 2. add oauth_* parameters
-        let mut signer = signer.oauth_parameters(consumer_key, &options);
-        // Step 3. key (oauth_ ~ z]
-        for (key, value) in query_after_oauth {
-            if !key.starts_with(OAUTH_KEY_PREFIX) {
-                // not an oauth_* parameter
-                signer.parameter(key, value);
+        match body_hash {
+            Some(body_hash) => {
+                let value = percent_encode(&body_hash).to_string();
+                items.push((OAUTH_BODY_HASH_KEY.to_string(), value, OAuthItem::Parameter));
             }
+            // e.g. PLAINTEXT does not hash the body
+            None if params.strict && body.is_some() => return Err(SignerError::UnsignedBody),
+            None => {}
+        }
+        items.sort_by(|(k1, _, _), (k2, _, _)| k1.cmp(k2));
+        let parameters = items
+            .iter()
+            .map(|(key, value, _)| (key.clone(), value.clone()))
+            .collect();
+
+        // Step 2. destructure query and percent-encode each key and value
+        // (oauth_* parameters in the query are already captured)
+        items.extend(
+            form_urlencoded::parse(payload.as_bytes())
+                .filter(|(k, _)| !k.starts_with(OAUTH_KEY_PREFIX))
+                .map(|(k, v)| {
+                    (
+                        percent_encode(&k).to_string(),
+                        percent_encode(&v).to_string(),
+                        OAuthItem::Parameter,
+                    )
+                }),
+        );
+
+        // Step 3. sort the encoded items by alphabetical order
+        // (that is required by OAuth specification)
+        // cf. https://tools.ietf.org/html/rfc5849#section-3.4.1.3.2
+        items.sort_by(|(k1, v1, _), (k2, v2, _)| (k1, v1).cmp(&(k2, v2)));
+        for (i, (key, value, item)) in items.iter().enumerate() {
+            if i > 0 {
+                sign.delimiter();
+            }
+            item.append_to(&mut sign, key, value);
         }
 
         // signature is generated.
-        let sign = signer.finish().authorization;
+        let signature = sign.try_finish()?.to_string();
+        let base_string = base_string.borrow().clone();
+        Ok(SignedParameters {
+            parameters,
+            signature,
+            realm: params.realm.map(|r| r.into_owned()),
+            base_string,
+            signing_key: redacted_signing_key(consumer_secret, token_secret),
+            transmission: params.transmission,
+        })
+    }
+}
+
+/// The kind of the parameter, to call the corresponding method of the `Sign`.
+#[derive(Debug, Clone, Copy)]
+enum OAuthItem {
+    Parameter,
+    Callback,
+    ConsumerKey,
+    Nonce,
+    SignatureMethod(&'static str),
+    Timestamp(u64),
+    Token,
+    Verifier,
+    Version,
+}
+
+impl OAuthItem {
+    /// Append the percent-encoded parameter to the sign.
+    fn append_to<S: Sign>(self, sign: &mut S, key: &str, value: &str) {
+        // the parameters are encoded twice in the signature base string
+        let value = percent_encode(value);
+        match self {
+            // the key is written as it is, so it is encoded once more as the value is
+            OAuthItem::Parameter => sign.parameter(&percent_encode(key).to_string(), value),
+            OAuthItem::Callback => sign.callback(OAUTH_CALLBACK_KEY, value),
+            OAuthItem::ConsumerKey => sign.consumer_key(OAUTH_CONSUMER_KEY, value),
+            OAuthItem::Nonce => sign.nonce(OAUTH_NONCE_KEY, value),
+            OAuthItem::SignatureMethod(name) => {
+                sign.signature_method(OAUTH_SIGNATURE_METHOD_KEY, name)
+            }
+            OAuthItem::Timestamp(timestamp) => sign.timestamp(OAUTH_TIMESTAMP_KEY, timestamp),
+            OAuthItem::Token => sign.token(OAUTH_TOKEN_KEY, value),
+            OAuthItem::Verifier => sign.verifier(OAUTH_VERIFIER_KEY, value),
+            OAuthItem::Version => sign.version(OAUTH_VERSION_KEY, OAUTH_VERSION_VALUE),
+        }
+    }
+}
+
+/// The `oauth_*` parameters and the signature generated by the `Signer`.
+#[derive(Debug, Clone)]
+pub struct SignedParameters {
+    /// `oauth_*` parameters except `oauth_signature`, with percent-encoded keys and values.
+    parameters: Vec<(String, String)>,
+    /// percent-encoded `oauth_signature` value.
+    signature: String,
+    realm: Option<String>,
+    base_string: String,
+    /// signing key with the secrets redacted.
+    signing_key: String,
+    transmission: ParameterTransmission,
+}
 
-        if let Some(realm) = params.realm {
+impl SignedParameters {
+    /// The signature base string used to generate the signature.
+    pub fn base_string(&self) -> &str {
+        &self.base_string
+    }
+
+    /// The transmission mode configured with the `OAuthParameters`.
+    pub fn transmission(&self) -> ParameterTransmission {
+        self.transmission
+    }
+
+    /// Replace the signature with the percent-encoded value.
+    #[cfg(feature = "reqwest")]
+    pub(crate) fn set_signature(&mut self, signature: String) {
+        self.signature = signature;
+    }
+
+    /// Build the value of the Authorization header.
+    pub fn authorization_header(&self) -> String {
+        let mut header = String::from("OAuth ");
+        for (key, value) in self.parameters.iter() {
+            write!(header, "{}=\"{}\",", key, value).unwrap();
+        }
+        write!(header, "{}=\"{}\"", OAUTH_SIGNATURE_KEY, self.signature).unwrap();
+        if let Some(ref realm) = self.realm {
             // OAuth oauth_...,realm="realm"
-            Ok(format!("{},{}=\"{}\"", sign, REALM_KEY, realm.as_ref()))
-        } else {
-            // OAuth oauth_...
-            Ok(sign)
+            write!(header, ",{}=\"{}\"", REALM_KEY, realm).unwrap();
         }
+        header
+    }
+
+    /// Build the report of this signing.
+    pub fn report(&self) -> SigningReport {
+        SigningReport::new(
+            self.base_string.clone(),
+            self.signing_key.clone(),
+            self.authorization_header(),
+        )
+    }
+
+    /// Decoded `oauth_*` parameters including `oauth_signature`, to be sent
+    /// as the query or the form.
+    ///
+    /// The realm is not included, it is only for the Authorization header.
+    pub fn parameter_pairs(&self) -> Vec<(String, String)> {
+        self.parameters
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .chain(core::iter::once((
+                OAUTH_SIGNATURE_KEY,
+                self.signature.as_str(),
+            )))
+            .map(|(k, v)| {
+                (
+                    percent_decode_str(k).decode_utf8_lossy().into_owned(),
+                    percent_decode_str(v).decode_utf8_lossy().into_owned(),
+                )
+            })
+            .collect()
     }
 }
 
-fn generate_signer<TSM>(
-    signature_method: TSM,
-    method: &str,
-    url: Url,
-    consumer_secret: &str,
-    token_secret: Option<&str>,
-    is_url_query: bool,
-) -> OAuthSigner<TSM>
+/// Check the key is the parameter defined in the protocol, that could not be
+/// specified as an extension parameter.
+fn is_reserved_key(key: &str) -> bool {
+    matches!(
+        key,
+        OAUTH_CALLBACK_KEY
+            | OAUTH_CONSUMER_KEY
+            | OAUTH_NONCE_KEY
+            | OAUTH_SIGNATURE_KEY
+            | OAUTH_SIGNATURE_METHOD_KEY
+            | OAUTH_TIMESTAMP_KEY
+            | OAUTH_TOKEN_KEY
+            | OAUTH_VERIFIER_KEY
+            | OAUTH_VERSION_KEY
+            | REALM_KEY
+    )
+}
+
+/// Build the signing key `consumer_secret&token_secret` with the secrets redacted.
+fn redacted_signing_key(consumer_secret: &str, token_secret: Option<&str>) -> String {
+    let redact = |secret: &str| if secret.is_empty() { "" } else { "[REDACTED]" };
+    format!(
+        "{}&{}",
+        redact(consumer_secret),
+        token_secret.map_or("", redact)
+    )
+}
+
+/// Wraps the signature method to capture the signature base string.
+#[derive(Debug, Clone)]
+struct Capture<TSM> {
+    inner: TSM,
+    base_string: Rc<RefCell<String>>,
+}
+
+struct CaptureSign<S> {
+    inner: S,
+    base_string: Rc<RefCell<String>>,
+}
+
+impl<TSM> SignatureMethod for Capture<TSM>
 where
     TSM: SignatureMethod,
 {
-    if is_url_query {
-        OAuthSigner::with_signature_method(
-            signature_method,
-            method,
-            url,
-            consumer_secret,
-            token_secret,
-        )
-    } else {
-        OAuthSigner::form_with_signature_method(
-            signature_method,
-            method,
-            url,
-            consumer_secret,
-            token_secret,
-        )
+    type Sign = CaptureSign<TSM::Sign>;
+
+    fn sign_with(
+        self,
+        consumer_secret: impl Display,
+        token_secret: Option<impl Display>,
+    ) -> Self::Sign {
+        CaptureSign {
+            inner: self.inner.sign_with(consumer_secret, token_secret),
+            base_string: self.base_string,
+        }
+    }
+}
+
+impl<S> CaptureSign<S> {
+    fn capture_parameter(&self, key: &str, value: &str) {
+        write!(self.base_string.borrow_mut(), "{}%3D{}", key, value).unwrap();
+    }
+}
+
+impl<S> Sign for CaptureSign<S>
+where
+    S: Sign,
+{
+    type Signature = S::Signature;
+
+    fn get_signature_method_name(&self) -> &'static str {
+        self.inner.get_signature_method_name()
+    }
+
+    fn request_method(&mut self, method: &str) {
+        write!(self.base_string.borrow_mut(), "{}&", method).unwrap();
+        self.inner.request_method(method);
+    }
+
+    fn uri(&mut self, uri: impl Display) {
+        let uri = uri.to_string();
+        write!(self.base_string.borrow_mut(), "{}&", uri).unwrap();
+        self.inner.uri(uri);
+    }
+
+    fn parameter(&mut self, key: &str, value: impl Display) {
+        let value = value.to_string();
+        self.capture_parameter(key, &value);
+        self.inner.parameter(key, value);
+    }
+
+    fn delimiter(&mut self) {
+        self.base_string.borrow_mut().push_str("%26");
+        self.inner.delimiter();
+    }
+
+    fn finish(self) -> Self::Signature {
+        self.inner.finish()
+    }
+
+    fn try_finish(self) -> SignResult<Self::Signature> {
+        self.inner.try_finish()
+    }
+
+    fn callback(&mut self, default_key: &'static str, value: impl Display) {
+        let value = value.to_string();
+        self.capture_parameter(default_key, &value);
+        self.inner.callback(default_key, value);
+    }
+
+    fn consumer_key(&mut self, default_key: &'static str, value: impl Display) {
+        let value = value.to_string();
+        self.capture_parameter(default_key, &value);
+        self.inner.consumer_key(default_key, value);
+    }
+
+    fn nonce(&mut self, default_key: &'static str, value: impl Display) {
+        let value = value.to_string();
+        self.capture_parameter(default_key, &value);
+        self.inner.nonce(default_key, value);
+    }
+
+    fn use_nonce(&self) -> bool {
+        self.inner.use_nonce()
+    }
+
+    fn signature_method(&mut self, default_key: &'static str, default_value: &'static str) {
+        self.capture_parameter(default_key, default_value);
+        self.inner.signature_method(default_key, default_value);
+    }
+
+    fn timestamp(&mut self, default_key: &'static str, value: u64) {
+        self.capture_parameter(default_key, &value.to_string());
+        self.inner.timestamp(default_key, value);
+    }
+
+    fn use_timestamp(&self) -> bool {
+        self.inner.use_timestamp()
+    }
+
+    fn token(&mut self, default_key: &'static str, value: impl Display) {
+        let value = value.to_string();
+        self.capture_parameter(default_key, &value);
+        self.inner.token(default_key, value);
+    }
+
+    fn verifier(&mut self, default_key: &'static str, value: impl Display) {
+        let value = value.to_string();
+        self.capture_parameter(default_key, &value);
+        self.inner.verifier(default_key, value);
+    }
+
+    fn version(&mut self, default_key: &'static str, default_value: &'static str) {
+        self.capture_parameter(default_key, default_value);
+        self.inner.version(default_key, default_value);
     }
 }
 
@@ -191,10 +604,6 @@ Represents OAuth parameters including oauth_nonce, oauth_timestamp, realm, and o
 ```rust
 use reqwest_oauth1::*;
 
-#[cfg(feature = "blocking")]
-use reqwest::blocking::Client as Client;
-
-#[cfg(not(feature = "blocking"))]
 use reqwest::Client;
 
 let consumer_key = "[CONSUMER_KEY]";
@@ -225,10 +634,6 @@ with the specified one in the OAuthParameters.
 ```rust
 use reqwest_oauth1::*;
 
-#[cfg(feature = "blocking")]
-use reqwest::blocking::Client as Client;
-
-#[cfg(not(feature = "blocking"))]
 use reqwest::Client;
 
 let consumer_key = "[CONSUMER_KEY]";
@@ -258,6 +663,28 @@ where
     timestamp: Option<u64>,
     verifier: Option<Cow<'a, str>>,
     version: bool,
+    allow_insecure_plaintext: bool,
+    strict: bool,
+    transmission: ParameterTransmission,
+    extensions: BTreeMap<Cow<'a, str>, Cow<'a, str>>,
+    clock: Option<Arc<dyn Clock>>,
+    nonce_generator: Option<Arc<dyn NonceGenerator>>,
+}
+
+/// Where the OAuth parameters are transmitted in the request.
+///
+/// cf. https://tools.ietf.org/html/rfc5849#section-3.5
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParameterTransmission {
+    /// `Authorization` header (RFC 5849 section 3.5.1).
+    #[default]
+    AuthorizationHeader,
+    /// urlencoded form body (RFC 5849 section 3.5.2).
+    ///
+    /// The request body must be an urlencoded form or empty.
+    FormBody,
+    /// query string of the request URL (RFC 5849 section 3.5.3).
+    QueryString,
 }
 
 impl Default for OAuthParameters<'static, HmacSha1> {
@@ -270,6 +697,12 @@ impl Default for OAuthParameters<'static, HmacSha1> {
             timestamp: None,
             verifier: None,
             version: false,
+            allow_insecure_plaintext: false,
+            strict: false,
+            transmission: ParameterTransmission::AuthorizationHeader,
+            extensions: BTreeMap::new(),
+            clock: None,
+            nonce_generator: None,
         }
     }
 }
@@ -296,6 +729,11 @@ where
     }
 
     /// set the oauth_nonce value
+    ///
+    /// # Note
+    /// The same nonce is sent with every request signed with these parameters,
+    /// and servers reject the reused nonce as a replay.
+    /// Use `nonce_generator` to generate the nonce for each request.
     pub fn nonce<T>(self, nonce: T) -> Self
     where
         T: Into<Cow<'a, str>>,
@@ -332,6 +770,36 @@ where
         }
     }
 
+    /// set the clock to generate the oauth_timestamp value for each request
+    ///
+    /// # Note
+    /// The system clock is used by default (with the `std` feature).
+    /// The value specified with `timestamp` takes precedence over the clock.
+    pub fn clock<T>(self, clock: T) -> Self
+    where
+        T: Clock + 'static,
+    {
+        OAuthParameters {
+            clock: Some(Arc::new(clock)),
+            ..self
+        }
+    }
+
+    /// set the generator of the oauth_nonce value for each request
+    ///
+    /// # Note
+    /// The random nonce is used by default (with the `std` feature).
+    /// The value specified with `nonce` takes precedence over the generator.
+    pub fn nonce_generator<T>(self, nonce_generator: T) -> Self
+    where
+        T: NonceGenerator + 'static,
+    {
+        OAuthParameters {
+            nonce_generator: Some(Arc::new(nonce_generator)),
+            ..self
+        }
+    }
+
     /// set the oauth_verifier value
     pub fn verifier<T>(self, verifier: T) -> Self
     where
@@ -358,49 +826,468 @@ where
             ..self
         }
     }
+
+    /// allow the PLAINTEXT signature method to sign the request not sent over https.
+    ///
+    /// # Note
+    /// The PLAINTEXT signature method sends your secrets as the signature.
+    /// The signer refuses to sign the request to any non-`https` URL by default,
+    /// otherwise your secrets may leak in cleartext.
+    pub fn allow_insecure_plaintext<T>(self, allow: T) -> Self
+    where
+        T: Into<bool>,
+    {
+        OAuthParameters {
+            allow_insecure_plaintext: allow.into(),
+            ..self
+        }
+    }
+
+    /// refuse to send the request which could not be signed correctly.
+    ///
+    /// # Note
+    /// By default, the request is sent even when it is signed partially,
+    /// e.g. the URL could not be parsed, or the body is not covered by the signature.
+    /// In the strict mode, the signing fails instead, so neither unsigned
+    /// nor mis-signed requests are sent.
+    pub fn strict<T>(self, strict: T) -> Self
+    where
+        T: Into<bool>,
+    {
+        OAuthParameters {
+            strict: strict.into(),
+            ..self
+        }
+    }
+
+    /// set how the OAuth parameters are transmitted to the server
+    ///
+    /// # Note
+    /// The parameters are sent with the Authorization header by default.
+    /// You can override this for each request with `RequestBuilder::transmission`.
+    pub fn transmission(self, transmission: ParameterTransmission) -> Self {
+        OAuthParameters {
+            transmission,
+            ..self
+        }
+    }
+
+    /// add the extension parameter, e.g. `oauth_session_handle`, `xoauth_*`
+    ///
+    /// # Note
+    /// The extension parameters are signed and sent with the other `oauth_*` parameters.
+    /// The parameters defined in the protocol (e.g. `oauth_nonce`) could not be
+    /// specified, the signing fails with `SignerError::UnconfigurableParameter`.
+    /// The `oauth_body_hash` is computed from the request body, so the signing
+    /// also fails when the specified one does not match it.
+    pub fn extension<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<Cow<'a, str>>,
+        V: Into<Cow<'a, str>>,
+    {
+        self.extensions.insert(key.into(), value.into());
+        self
+    }
+
+    /// set the signature method, e.g. `HmacSha256`
+    ///
+    /// # Note
+    /// The other parameters configured before are kept.
     pub fn signature_method<T>(self, signature_method: T) -> OAuthParameters<'a, T>
     where
         T: SignatureMethod + Clone,
     {
         OAuthParameters {
             signature_method,
-            callback: None,
-            nonce: None,
-            realm: None,
-            timestamp: None,
-            verifier: None,
-            version: false,
+            callback: self.callback,
+            nonce: self.nonce,
+            realm: self.realm,
+            timestamp: self.timestamp,
+            verifier: self.verifier,
+            version: self.version,
+            allow_insecure_plaintext: self.allow_insecure_plaintext,
+            strict: self.strict,
+            transmission: self.transmission,
+            extensions: self.extensions,
+            clock: self.clock,
+            nonce_generator: self.nonce_generator,
         }
     }
 }
 
-impl<T> OAuthParameters<'_, T>
+impl<'a, T> OAuthParameters<'a, T>
 where
     T: SignatureMethod + Clone,
 {
-    fn build_options<'a>(&'a self, token: Option<&'a str>) -> Options<'a> {
-        let mut opt = Options::new();
-
-        // NOTE: items must be added by alphabetical order
+    /// Collect the `oauth_*` parameters with the percent-encoded values.
+    fn oauth_items<S: Sign>(
+        &self,
+        sign: &S,
+        consumer_key: &str,
+        token: Option<&str>,
+    ) -> SignResult<Vec<(String, String, OAuthItem)>> {
+        let mut items = Vec::new();
+        let mut push = |key: &str, value: &str, item: OAuthItem| {
+            items.push((key.to_string(), percent_encode(value).to_string(), item));
+        };
 
         if let Some(ref callback) = self.callback {
-            opt.callback(callback.as_ref());
+            push(OAUTH_CALLBACK_KEY, callback, OAuthItem::Callback);
         }
-        if let Some(ref nonce) = self.nonce {
-            opt.nonce(nonce.as_ref());
+        push(OAUTH_CONSUMER_KEY, consumer_key, OAuthItem::ConsumerKey);
+        if sign.use_nonce() {
+            let nonce = match self.nonce {
+                Some(ref nonce) => nonce.to_string(),
+                None => self.generate_nonce()?,
+            };
+            push(OAUTH_NONCE_KEY, &nonce, OAuthItem::Nonce);
         }
-        if let Some(timestamp) = self.timestamp {
-            opt.timestamp(timestamp);
+        let signature_method = sign.get_signature_method_name();
+        push(
+            OAUTH_SIGNATURE_METHOD_KEY,
+            signature_method,
+            OAuthItem::SignatureMethod(signature_method),
+        );
+        if sign.use_timestamp() {
+            let timestamp = match self.timestamp {
+                Some(timestamp) => timestamp,
+                None => self.current_timestamp()?,
+            };
+            push(
+                OAUTH_TIMESTAMP_KEY,
+                &timestamp.to_string(),
+                OAuthItem::Timestamp(timestamp),
+            );
         }
         if let Some(token) = token {
-            opt.token(token);
+            push(OAUTH_TOKEN_KEY, token, OAuthItem::Token);
         }
         if let Some(ref verifier) = self.verifier {
-            opt.verifier(verifier.as_ref());
+            push(OAUTH_VERIFIER_KEY, verifier, OAuthItem::Verifier);
+        }
+        if self.version {
+            push(OAUTH_VERSION_KEY, OAUTH_VERSION_VALUE, OAuthItem::Version);
+        }
+        for (key, value) in self.extensions.iter() {
+            // the key is percent-encoded as same as the keys of the payload
+            push(
+                &percent_encode(key).to_string(),
+                value,
+                OAuthItem::Parameter,
+            );
+        }
+
+        Ok(items)
+    }
+
+    /// Generate the nonce with the `NonceGenerator`, or the random nonce by default.
+    fn generate_nonce(&self) -> SignResult<String> {
+        match self.nonce_generator {
+            Some(ref generator) => Ok(generator.generate()),
+            #[cfg(feature = "std")]
+            None => Ok(crate::RandomNonce.generate()),
+            // no random source is available without std
+            #[cfg(not(feature = "std"))]
+            None => Err(SignerError::MissingParameter(OAUTH_NONCE_KEY)),
         }
-        opt.version(self.version);
-        if self.version {}
+    }
+
+    /// Read the current UNIX time from the `Clock`, or the system clock by default.
+    fn current_timestamp(&self) -> SignResult<u64> {
+        match self.clock {
+            Some(ref clock) => Ok(clock.now()),
+            #[cfg(feature = "std")]
+            None => Ok(crate::SystemClock.now()),
+            // no clock is available without std
+            #[cfg(not(feature = "std"))]
+            None => Err(SignerError::MissingParameter(OAUTH_TIMESTAMP_KEY)),
+        }
+    }
+
+    /// Ensure the extension parameters do not conflict with the parameters
+    /// defined in the protocol.
+    fn check_extensions(&self) -> SignResult<()> {
+        match self.extensions.keys().find(|key| is_reserved_key(key)) {
+            Some(key) => Err(SignerError::UnconfigurableParameter(key.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    /// The `oauth_signature_method` value of the signature method.
+    fn signature_method_name(&self) -> &'static str {
+        self.signature_method
+            .clone()
+            .sign_with("", None::<&str>)
+            .get_signature_method_name()
+    }
+
+    /// Ensure the secrets are not sent in cleartext with the PLAINTEXT signature method.
+    fn check_transport(&self, url: &str) -> SignResult<()> {
+        let is_https = url
+            .split_once(':')
+            .map_or(false, |(scheme, _)| scheme.eq_ignore_ascii_case("https"));
+        if self.allow_insecure_plaintext || is_https {
+            return Ok(());
+        }
+        if self.signature_method_name() == PLAINTEXT_METHOD_NAME {
+            // the query may carry the secrets, so only the origin is reported
+            Err(SignerError::InsecureTransport(url_origin(url)?))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SignedParameters;
+    use crate::{HmacSha256, OAuthParameters, Secrets, Signer};
+    use alloc::string::String;
+
+    #[test]
+    fn sign_plain_request() {
+        // https://tools.ietf.org/html/rfc5849#section-3.4.1.1
+        let secrets = Secrets::new("9djdj82h48djs9d2", "j49sk3j29djd")
+            .token("kkk9d7dh3k39sjv7", "dh893hdasih9");
+        let params = OAuthParameters::new()
+            .nonce("7d8f3e4a")
+            .timestamp(137_131_201u64)
+            .realm("Example");
+        let signed = Signer::new(secrets, params)
+            .sign(
+                "post",
+                "http://example.com/request?b5=%3D%253D&a3=a&c%40=&a2=r%20b",
+                &[("c2", ""), ("a3", "2 q")],
+            )
+            .unwrap();
 
-        opt
+        assert_eq!(
+            signed.base_string(),
+            "POST&http%3A%2F%2Fexample.com%2Frequest&a2%3Dr%2520b%26a3%3D2%2520q\
+             %26a3%3Da%26b5%3D%253D%25253D%26c%2540%3D%26c2%3D%26oauth_consumer_\
+             key%3D9djdj82h48djs9d2%26oauth_nonce%3D7d8f3e4a%26oauth_signature_m\
+             ethod%3DHMAC-SHA1%26oauth_timestamp%3D137131201%26oauth_token%3Dkkk\
+             9d7dh3k39sjv7"
+        );
+        assert_eq!(
+            signed.authorization_header(),
+            "OAuth oauth_consumer_key=\"9djdj82h48djs9d2\",oauth_nonce=\"7d8f3e4a\",\
+             oauth_signature_method=\"HMAC-SHA1\",oauth_timestamp=\"137131201\",\
+             oauth_token=\"kkk9d7dh3k39sjv7\",oauth_signature=\"r6%2FTJjbCOr97%2F%2BUU0NsvSne7s5g%3D\",\
+             realm=\"Example\""
+        );
+    }
+
+    /// The `oauth_signature` value, decoded.
+    fn signature_of(signed: &SignedParameters) -> String {
+        signed
+            .parameter_pairs()
+            .into_iter()
+            .find(|(key, _)| key == "oauth_signature")
+            .map(|(_, value)| value)
+            .unwrap()
+    }
+
+    #[test]
+    fn rfc5849_normalize_parameters() {
+        // https://tools.ietf.org/html/rfc5849#section-3.4.1.3
+        let secrets = Secrets::new("9djdj82h48djs9d2", "j49sk3j29djd")
+            .token("kkk9d7dh3k39sjv7", "dh893hdasih9");
+        let params = OAuthParameters::new()
+            .nonce("7d8f3e4a")
+            .timestamp(137_131_201u64)
+            .realm("Example");
+        let signed = Signer::new(secrets, params)
+            .sign(
+                "POST",
+                "http://example.com/request?b5=%3D%253D&a3=a&c%40=&a2=r%20b",
+                &[("c2", ""), ("a3", "2 q")],
+            )
+            .unwrap();
+
+        // realm is excluded, and the duplicated names are sorted by the values
+        let parameters = signed.report().parameters;
+        let expected = [
+            ("a2", "r%20b"),
+            ("a3", "2%20q"),
+            ("a3", "a"),
+            ("b5", "%3D%253D"),
+            ("c%40", ""),
+            ("c2", ""),
+            ("oauth_consumer_key", "9djdj82h48djs9d2"),
+            ("oauth_nonce", "7d8f3e4a"),
+            ("oauth_signature_method", "HMAC-SHA1"),
+            ("oauth_timestamp", "137131201"),
+            ("oauth_token", "kkk9d7dh3k39sjv7"),
+        ];
+        assert_eq!(parameters.len(), expected.len());
+        for ((key, value), (expected_key, expected_value)) in parameters.iter().zip(expected) {
+            assert_eq!(
+                (key.as_str(), value.as_str()),
+                (expected_key, expected_value)
+            );
+        }
+    }
+
+    #[test]
+    fn rfc5849_temporary_credentials() {
+        // https://tools.ietf.org/html/rfc5849#section-1.2
+        let params = OAuthParameters::new()
+            .nonce("wIjqoS")
+            .timestamp(137_131_200u64)
+            .callback("http://printer.example.com/ready")
+            .realm("Photos");
+        let signed = Signer::new(Secrets::new("dpf43f3p2l4k3l03", "kd94hf93k423kf44"), params)
+            .sign(
+                "POST",
+                "https://photos.example.net/initiate",
+                &[] as &[(&str, &str)],
+            )
+            .unwrap();
+
+        assert_eq!(
+            signed.base_string(),
+            "POST&https%3A%2F%2Fphotos.example.net%2Finitiate&oauth_callback%3Dhttp%253A%252F%252F\
+             printer.example.com%252Fready%26oauth_consumer_key%3Ddpf43f3p2l4k3l03%26oauth_nonce\
+             %3DwIjqoS%26oauth_signature_method%3DHMAC-SHA1%26oauth_timestamp%3D137131200"
+        );
+        assert_eq!(signature_of(&signed), "74KNZJeDHnMBp0EMJ9ZHt/XKycU=");
+    }
+
+    #[test]
+    fn rfc5849_token_credentials() {
+        // https://tools.ietf.org/html/rfc5849#section-1.2
+        let secrets = Secrets::new("dpf43f3p2l4k3l03", "kd94hf93k423kf44")
+            .token("hh5s93j4hdidpola", "hdhd0244k9j7ao03");
+        let params = OAuthParameters::new()
+            .nonce("walatlh")
+            .timestamp(137_131_201u64)
+            .verifier("hfdp7dh39dks9884")
+            .realm("Photos");
+        let signed = Signer::new(secrets, params)
+            .sign(
+                "POST",
+                "https://photos.example.net/token",
+                &[] as &[(&str, &str)],
+            )
+            .unwrap();
+
+        assert_eq!(
+            signed.base_string(),
+            "POST&https%3A%2F%2Fphotos.example.net%2Ftoken&oauth_consumer_key%3Ddpf43f3p2l4k3l03\
+             %26oauth_nonce%3Dwalatlh%26oauth_signature_method%3DHMAC-SHA1%26oauth_timestamp\
+             %3D137131201%26oauth_token%3Dhh5s93j4hdidpola%26oauth_verifier%3Dhfdp7dh39dks9884"
+        );
+        assert_eq!(signature_of(&signed), "gKgrFCywp7rO0OXSjdot/IHF7IU=");
+    }
+
+    #[test]
+    fn rfc5849_protected_resource() {
+        // https://tools.ietf.org/html/rfc5849#section-1.2
+        let secrets = Secrets::new("dpf43f3p2l4k3l03", "kd94hf93k423kf44")
+            .token("nnch734d00sl2jdk", "pfkkdhi9sl3r4s00");
+        let params = OAuthParameters::new()
+            .nonce("chapoH")
+            .timestamp(137_131_202u64)
+            .realm("Photos");
+        let signed = Signer::new(secrets, params)
+            .sign(
+                "GET",
+                "http://photos.example.net/photos?file=vacation.jpg&size=original",
+                &[] as &[(&str, &str)],
+            )
+            .unwrap();
+
+        assert_eq!(
+            signed.base_string(),
+            "GET&http%3A%2F%2Fphotos.example.net%2Fphotos&file%3Dvacation.jpg%26\
+             oauth_consumer_key%3Ddpf43f3p2l4k3l03%26oauth_nonce%3DchapoH%26\
+             oauth_signature_method%3DHMAC-SHA1%26oauth_timestamp%3D137131202%26\
+             oauth_token%3Dnnch734d00sl2jdk%26size%3Doriginal"
+        );
+        assert_eq!(signature_of(&signed), "MdpQcU8iPSUjWoN/UDMsK2sui9I=");
+    }
+
+    #[test]
+    fn keep_parameters_with_signature_method() {
+        let params = OAuthParameters::new()
+            .nonce("7d8f3e4a")
+            .timestamp(137_131_201u64)
+            .realm("Example")
+            .signature_method(HmacSha256);
+        let signed = Signer::new(Secrets::new("consumer", "secret"), params)
+            .sign("GET", "https://example.com/", &[] as &[(&str, &str)])
+            .unwrap();
+        let header = signed.authorization_header();
+        assert!(header.contains("oauth_nonce=\"7d8f3e4a\""));
+        assert!(header.contains("oauth_signature_method=\"HMAC-SHA256\""));
+        assert!(header.contains("oauth_timestamp=\"137131201\""));
+        assert!(header.contains("realm=\"Example\""));
+    }
+
+    #[test]
+    fn capture_plain_oauth_parameters() {
+        let signed = Signer::new(Secrets::new("consumer", "secret"), OAuthParameters::new())
+            .sign(
+                "GET",
+                "https://example.com/?oauth_nonce=QueryNonce",
+                &[("oauth_timestamp", "100")],
+            )
+            .unwrap();
+        let header = signed.authorization_header();
+        assert!(header.contains("oauth_nonce=\"QueryNonce\""));
+        assert!(header.contains("oauth_timestamp=\"100\""));
+
+        // realm is not signed, but sent in the Authorization header
+        let params = OAuthParameters::new().nonce("nonce").timestamp(100u64);
+        let signed = Signer::new(Secrets::new("consumer", "secret"), params)
+            .sign("GET", "https://example.com/", &[("realm", "Photos")])
+            .unwrap();
+        assert!(!signed.base_string().contains("realm"));
+        assert!(signed.authorization_header().contains("realm=\"Photos\""));
+    }
+
+    #[test]
+    fn encode_extension_keys() {
+        let params = OAuthParameters::new()
+            .nonce("nonce")
+            .timestamp(100u64)
+            .extension("xoauth_ext:id", "1");
+        let signed = Signer::new(Secrets::new("consumer", "secret"), params)
+            .sign("GET", "https://example.com/", &[("xoauth_ext0", "2")])
+            .unwrap();
+
+        // sorted by the encoded keys, `%` precedes `0`
+        assert_eq!(
+            signed.base_string(),
+            "GET&https%3A%2F%2Fexample.com%2F&oauth_consumer_key%3Dconsumer%26\
+             oauth_nonce%3Dnonce%26oauth_signature_method%3DHMAC-SHA1%26\
+             oauth_timestamp%3D100%26xoauth_ext%253Aid%3D1%26xoauth_ext0%3D2"
+        );
+        assert!(signed
+            .authorization_header()
+            .contains("xoauth_ext%3Aid=\"1\""));
+        assert!(signed
+            .parameter_pairs()
+            .contains(&("xoauth_ext:id".into(), "1".into())));
+    }
+
+    #[cfg(not(feature = "std"))]
+    #[test]
+    fn require_nonce_without_std() {
+        use crate::SignerError;
+        use alloc::string::ToString;
+
+        let params = OAuthParameters::new().timestamp(100u64);
+        let result = Signer::new(Secrets::new("consumer", "secret"), params).sign(
+            "GET",
+            "https://example.com/",
+            &[("a", "b")],
+        );
+        assert!(matches!(
+            result,
+            Err(SignerError::MissingParameter(key)) if key == "oauth_nonce"
+        ));
+        assert!(result.unwrap_err().to_string().contains("oauth_nonce"));
     }
 }
@@ -0,0 +1,255 @@
+use std::{
+    convert::TryFrom,
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        Arc,
+    },
+};
+
+use http::{header::DATE, HeaderMap, StatusCode};
+
+use crate::{Clock, OAuthProblem, SystemClock};
+
+/// The default tolerance of the clock skew in seconds.
+const DEFAULT_TOLERANCE: u64 = 60;
+
+/**
+Tracks the offset between the local clock and the server clock, and corrects
+the `oauth_timestamp` with it.
+
+The offset is learned from the `Date` header of the responses, or the
+`oauth_acceptable_timestamps` reported in the `WWW-Authenticate` header.
+When the server rejects the timestamp, the request is signed again with the
+corrected timestamp and sent once more, unless the `oauth_timestamp` or the
+`oauth_nonce` is fixed.
+
+The clones share the learned offset.
+
+# Basic usage
+
+```rust
+use reqwest_oauth1::*;
+
+use reqwest::Client;
+
+let consumer_key = "[CONSUMER_KEY]";
+let consumer_secret = "[CONSUMER_SECRET]";
+let secrets = reqwest_oauth1::Secrets::new(consumer_key, consumer_secret);
+
+let client = Client::new()
+    .oauth1(secrets)
+    .correct_clock_skew(ClockSkew::new());
+```
+
+*/
+#[derive(Debug, Clone)]
+pub struct ClockSkew {
+    inner: Arc<dyn Clock>,
+    offset: Arc<AtomicI64>,
+    tolerance: u64,
+}
+
+impl Default for ClockSkew {
+    fn default() -> Self {
+        ClockSkew::new()
+    }
+}
+
+impl ClockSkew {
+    /// Create a tracker of the system clock.
+    pub fn new() -> Self {
+        ClockSkew::with_clock(SystemClock)
+    }
+
+    /// Create a tracker of the specified local clock.
+    pub fn with_clock<T>(clock: T) -> Self
+    where
+        T: Clock + 'static,
+    {
+        ClockSkew {
+            inner: Arc::new(clock),
+            offset: Arc::new(AtomicI64::new(0)),
+            tolerance: DEFAULT_TOLERANCE,
+        }
+    }
+
+    /// set the tolerance of the skew in seconds (default: 60).
+    ///
+    /// # Note
+    /// A `401 Unauthorized` response is treated as the rejection of the
+    /// timestamp, only when the learned offset moves more than the tolerance.
+    pub fn tolerance(self, seconds: u64) -> Self {
+        ClockSkew {
+            tolerance: seconds,
+            ..self
+        }
+    }
+
+    /// The learned offset of the server clock from the local clock in seconds.
+    pub fn offset(&self) -> i64 {
+        self.offset.load(Ordering::Relaxed)
+    }
+
+    /// Learn the offset from the response, and returns `true` when the
+    /// request should be signed and sent again.
+    pub(crate) fn observe(&self, status: StatusCode, headers: &HeaderMap) -> bool {
+        let timestamp_refused = match OAuthProblem::from_headers(headers) {
+            Some(OAuthProblem::TimestampRefused {
+                acceptable_timestamps,
+            }) => Some(acceptable_timestamps),
+            _ => None,
+        };
+        // the range is reported by the server, so it must not overflow
+        let server_time = timestamp_refused
+            .flatten()
+            .and_then(|(min, max)| Some(min + max.checked_sub(min)? / 2))
+            .and_then(|time| i64::try_from(time).ok())
+            .or_else(|| {
+                headers
+                    .get(DATE)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(parse_http_date)
+            });
+        let Some(server_time) = server_time else {
+            return false;
+        };
+
+        let offset = server_time - self.inner.now() as i64;
+        let previous = self.offset.swap(offset, Ordering::Relaxed);
+        let rejected = status == StatusCode::UNAUTHORIZED || timestamp_refused.is_some();
+        rejected && offset.abs_diff(previous) > self.tolerance
+    }
+}
+
+impl Clock for ClockSkew {
+    fn now(&self) -> u64 {
+        let now = self.inner.now() as i64 + self.offset();
+        now.max(0) as u64
+    }
+}
+
+/// Parse the IMF-fixdate, e.g. `Sun, 06 Nov 1994 08:49:37 GMT`, into the UNIX time.
+///
+/// cf. https://tools.ietf.org/html/rfc7231#section-7.1.1.1
+fn parse_http_date(value: &str) -> Option<i64> {
+    let mut items = value.split_whitespace().skip(1);
+    let day = items.next()?.parse::<i64>().ok()?;
+    let month = match items.next()? {
+        "Jan" => 1,
+        "Feb" => 2,
+        "Mar" => 3,
+        "Apr" => 4,
+        "May" => 5,
+        "Jun" => 6,
+        "Jul" => 7,
+        "Aug" => 8,
+        "Sep" => 9,
+        "Oct" => 10,
+        "Nov" => 11,
+        "Dec" => 12,
+        _ => return None,
+    };
+    let year = items.next()?.parse::<i64>().ok()?;
+    let mut time = items.next()?.split(':').map(|t| t.parse::<i64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+    if items.next()? != "GMT" {
+        return None;
+    }
+
+    // days from the civil date
+    // cf. http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+    Some(days * 86_400 + hour * 3_600 + minute * 60 + second)
+}
+
+#[cfg(test)]
+mod tests {
+    use http::{header::WWW_AUTHENTICATE, HeaderValue};
+
+    use super::*;
+    use crate::FixedClock;
+
+    #[test]
+    fn parse_imf_fixdate() {
+        assert_eq!(
+            parse_http_date("Sun, 06 Nov 1994 08:49:37 GMT"),
+            Some(784_111_777)
+        );
+        assert_eq!(parse_http_date("Thu, 01 Jan 1970 00:00:00 GMT"), Some(0));
+        assert_eq!(parse_http_date("Sunday, 06-Nov-94 08:49:37 GMT"), None);
+    }
+
+    #[test]
+    fn learn_offset_from_date() {
+        let skew = ClockSkew::with_clock(FixedClock(784_111_000));
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            DATE,
+            HeaderValue::from_static("Sun, 06 Nov 1994 08:49:37 GMT"),
+        );
+
+        // learned, but the request was accepted
+        assert!(!skew.observe(StatusCode::OK, &headers));
+        assert_eq!(skew.offset(), 777);
+        assert_eq!(skew.now(), 784_111_777);
+
+        // the offset did not move, the request is not retried
+        assert!(!skew.observe(StatusCode::UNAUTHORIZED, &headers));
+    }
+
+    #[test]
+    fn learn_offset_from_problem() {
+        let skew = ClockSkew::with_clock(FixedClock(1_000));
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            DATE,
+            HeaderValue::from_static("Sun, 06 Nov 1994 08:49:37 GMT"),
+        );
+        headers.insert(
+            WWW_AUTHENTICATE,
+            HeaderValue::from_static(
+                "OAuth realm=\"Photos\", oauth_problem=\"timestamp_refused\", \
+                 oauth_acceptable_timestamps=\"1900-2100\"",
+            ),
+        );
+
+        assert!(skew.observe(StatusCode::BAD_REQUEST, &headers));
+        assert_eq!(skew.offset(), 1_000);
+        assert_eq!(skew.now(), 2_000);
+    }
+
+    #[test]
+    fn ignore_reversed_acceptable_timestamps() {
+        let skew = ClockSkew::with_clock(FixedClock(784_111_000));
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            WWW_AUTHENTICATE,
+            HeaderValue::from_static(
+                "OAuth oauth_problem=\"timestamp_refused\", \
+                 oauth_acceptable_timestamps=\"2100-1900\"",
+            ),
+        );
+        assert!(!skew.observe(StatusCode::UNAUTHORIZED, &headers));
+        assert_eq!(skew.offset(), 0);
+
+        // falls back to the Date header
+        headers.insert(
+            DATE,
+            HeaderValue::from_static("Sun, 06 Nov 1994 08:49:37 GMT"),
+        );
+        assert!(skew.observe(StatusCode::UNAUTHORIZED, &headers));
+        assert_eq!(skew.offset(), 777);
+    }
+
+    #[test]
+    fn ignore_response_without_time() {
+        let skew = ClockSkew::with_clock(FixedClock(1_000));
+        assert!(!skew.observe(StatusCode::UNAUTHORIZED, &HeaderMap::new()));
+        assert_eq!(skew.offset(), 0);
+    }
+}
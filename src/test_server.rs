@@ -0,0 +1,30 @@
+//! The local HTTP server for the tests sending the requests.
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+/// Serve the raw HTTP responses in order, one for each connection.
+///
+/// Returns the base URL of the server (e.g. `http://127.0.0.1:12345`), and the
+/// handle which resolves to the received request heads after the last response.
+pub(crate) async fn serve(responses: Vec<String>) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let server = tokio::spawn(async move {
+        let mut requests = Vec::new();
+        for response in responses {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let n = stream.read(&mut buf).await.unwrap();
+                request.extend_from_slice(&buf[..n]);
+            }
+            requests.push(String::from_utf8(request).unwrap());
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+        requests
+    });
+    (base, server)
+}
@@ -1,49 +1,240 @@
 use std::{collections::HashMap, future::Future};
 
 use async_trait::async_trait;
+use http::{header::CONTENT_TYPE, HeaderMap, StatusCode};
 use reqwest::Response;
-use serde::Deserialize;
+use serde::{de::DeserializeOwned, Deserialize};
 
-use crate::{Error, Result, TokenReaderError, TokenReaderResult};
+use crate::{Error, ProblemReport, Result, TokenReaderError, TokenReaderResult};
 
 const OAUTH_TOKEN_KEY: &str = "oauth_token";
 
 const OAUTH_TOKEN_SECRET_KEY: &str = "oauth_token_secret";
 
-/// Represents response of token acquisition.
+const OAUTH_CALLBACK_CONFIRMED_KEY: &str = "oauth_callback_confirmed";
+
+/**
+Represents response of token acquisition.
+
+The other contents are stored in `remain`, which is the map by default.
+You can deserialize them into your type with `parse_oauth_token_as`.
+
+```rust
+use reqwest_oauth1::{OAuthClientProvider, TokenReaderFuture, TokenResponse};
+use serde::Deserialize;
+
 #[derive(Deserialize, Debug)]
-pub struct TokenResponse {
+struct TwitterExtras {
+    user_id: u64,
+    screen_name: String,
+}
+
+async fn acquire_access_token() -> reqwest_oauth1::Result<TokenResponse<TwitterExtras>> {
+    let secrets = reqwest_oauth1::Secrets::new("[CONSUMER_KEY]", "[CONSUMER_SECRET]")
+        .token("[REQUEST_TOKEN]", "[REQUEST_TOKEN_SECRET]");
+    reqwest::Client::new()
+        .oauth1(secrets)
+        .post("https://api.twitter.com/oauth/access_token")
+        .oauth_verifier("[PIN]")
+        .send()
+        .parse_oauth_token_as::<TwitterExtras>()
+        .await
+}
+```
+
+*/
+#[derive(Deserialize, Debug)]
+pub struct TokenResponse<TExtras = HashMap<String, String>> {
     /// OAuth Token
     pub oauth_token: String,
     /// OAuth Token Secret
     pub oauth_token_secret: String,
     /// Other contents
     #[serde(flatten)]
-    pub remain: HashMap<String, String>,
+    pub remain: TExtras,
+}
+
+impl<TExtras> TokenResponse<TExtras>
+where
+    TExtras: DeserializeOwned,
+{
+    /// Parse the token from the body in the format of the `Content-Type`.
+    ///
+    /// The JSON body (`application/json`) is parsed with the `json` feature,
+    /// and the others are parsed as the urlencoded body.
+    pub fn from_body(content_type: Option<&str>, body: &str) -> TokenReaderResult<Self> {
+        #[cfg(feature = "json")]
+        if content_type.map(mime_of).is_some_and(is_json) {
+            return TokenResponse::from_json(body);
+        }
+        #[cfg(not(feature = "json"))]
+        let _ = content_type;
+        TokenResponse::from_urlencoded(body)
+    }
+
+    /// Parse the token from the urlencoded body,
+    /// e.g. `oauth_token=...&oauth_token_secret=...`.
+    pub fn from_urlencoded(body: &str) -> TokenReaderResult<Self> {
+        let pairs = form_urlencoded::parse(body.as_bytes()).into_owned();
+        TokenResponse::from_pairs(pairs, body)
+    }
+
+    /// Parse the token from the JSON object body.
+    ///
+    /// The values other than strings (e.g. numbers) are kept in `remain`
+    /// as the JSON texts, and `null` values are omitted.
+    #[cfg(feature = "json")]
+    pub fn from_json(body: &str) -> TokenReaderResult<Self> {
+        use serde_json::{Map, Value};
+
+        let object = serde_json::from_str::<Map<String, Value>>(body)
+            .map_err(|err| TokenReaderError::MalformedResponse(err.to_string()))?;
+        let pairs = object.into_iter().filter_map(|(k, v)| match v {
+            Value::String(v) => Some((k, v)),
+            Value::Null => None,
+            v => Some((k, v.to_string())),
+        });
+        TokenResponse::from_pairs(pairs, body)
+    }
+
+    /// The other contents are deserialized from the urlencoded form,
+    /// so the values could be typed (e.g. `u64` or `bool`) as same as the form body.
+    fn from_pairs<I>(pairs: I, body: &str) -> TokenReaderResult<Self>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut oauth_token = None;
+        let mut oauth_token_secret = None;
+        let mut remain = form_urlencoded::Serializer::new(String::new());
+        for (k, v) in pairs {
+            match k.as_str() {
+                OAUTH_TOKEN_KEY => oauth_token = Some(v),
+                OAUTH_TOKEN_SECRET_KEY => oauth_token_secret = Some(v),
+                _ => {
+                    // the callback is confirmed in the request token response (OAuth 1.0a)
+                    if k == OAUTH_CALLBACK_CONFIRMED_KEY && v != "true" {
+                        return Err(TokenReaderError::CallbackNotConfirmed(v));
+                    }
+                    remain.append_pair(&k, &v);
+                }
+            }
+        }
+        match (oauth_token, oauth_token_secret) {
+            (Some(t), Some(s)) => Ok(TokenResponse {
+                oauth_token: t,
+                oauth_token_secret: s,
+                remain: serde_urlencoded::from_str(&remain.finish())
+                    .map_err(|err| TokenReaderError::MalformedResponse(err.to_string()))?,
+            }),
+            (None, _) => Err(TokenReaderError::TokenKeyNotFound(
+                OAUTH_TOKEN_KEY,
+                body.to_string(),
+            )),
+            (_, _) => Err(TokenReaderError::TokenKeyNotFound(
+                OAUTH_TOKEN_SECRET_KEY,
+                body.to_string(),
+            )),
+        }
+    }
+}
+
+/// Represents the non-2xx response, e.g. of the token acquisition.
+#[derive(Debug, Clone)]
+pub struct ErrorResponse {
+    /// HTTP status code
+    pub status: StatusCode,
+    /// Response headers
+    pub headers: HeaderMap,
+    /// Response body
+    pub body: String,
+    /// Parameters of the urlencoded body, e.g. `oauth_problem`.
+    /// Empty when the body is not urlencoded (e.g. an HTML page).
+    pub parameters: HashMap<String, String>,
+}
+
+impl ErrorResponse {
+    /// Construct the response, and parse its body when it is urlencoded.
+    pub fn new(status: StatusCode, headers: HeaderMap, body: String) -> Self {
+        let parameters = if is_urlencoded(&headers) {
+            serde_urlencoded::from_str(&body).unwrap_or_default()
+        } else {
+            HashMap::new()
+        };
+        ErrorResponse {
+            status,
+            headers,
+            body,
+            parameters,
+        }
+    }
 }
 
 /// Add parse_oauth_token feature to reqwest::Response.
+///
+/// The responses with non-2xx status are returned as `Error::Problem` when
+/// `oauth_problem` is reported, otherwise `TokenReaderError::ErrorResponse`.
 // this trait is sealed
-#[async_trait(?Send)]
+#[async_trait]
 pub trait TokenReader: private::Sealed {
     async fn parse_oauth_token(self) -> Result<TokenResponse>;
+
+    /// Parse the token, and deserialize the other contents into `TExtras`.
+    async fn parse_oauth_token_as<TExtras>(self) -> Result<TokenResponse<TExtras>>
+    where
+        TExtras: DeserializeOwned;
+
+    /// Parse the token with your parser, which receives the `Content-Type` and the body,
+    /// e.g. into `TokenResponse<TExtras>` with your `TExtras`.
+    async fn parse_oauth_token_with<TExtras, F>(self, parser: F) -> Result<TokenResponse<TExtras>>
+    where
+        F: FnOnce(Option<&str>, &str) -> TokenReaderResult<TokenResponse<TExtras>> + Send;
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl TokenReader for Response {
     async fn parse_oauth_token(self) -> Result<TokenResponse> {
+        self.parse_oauth_token_as().await
+    }
+
+    async fn parse_oauth_token_as<TExtras>(self) -> Result<TokenResponse<TExtras>>
+    where
+        TExtras: DeserializeOwned,
+    {
+        let status = self.status();
+        let headers = self.headers().clone();
+        let text = self.text().await?;
+        read_token_response(status, headers, text, TokenResponse::from_body)
+    }
+
+    async fn parse_oauth_token_with<TExtras, F>(self, parser: F) -> Result<TokenResponse<TExtras>>
+    where
+        F: FnOnce(Option<&str>, &str) -> TokenReaderResult<TokenResponse<TExtras>> + Send,
+    {
+        let status = self.status();
+        let headers = self.headers().clone();
         let text = self.text().await?;
-        // let text = self.error_for_status()?.text().await?;
-        // println!("{:#?}", text);
-        Ok(read_oauth_token(text)?)
+        read_token_response(status, headers, text, parser)
     }
 }
 
 /// Add parse_oauth_token feature to Future of reqwest::Response.
+///
+/// The returned future is `Send`, so it can be spawned on the multi-threaded runtime.
 // this trait is also sealed
-#[async_trait(?Send)]
+#[async_trait]
 pub trait TokenReaderFuture: private::SealedWrapper {
     async fn parse_oauth_token(self) -> Result<TokenResponse>;
+
+    /// Parse the token, and deserialize the other contents into `TExtras`.
+    async fn parse_oauth_token_as<TExtras>(self) -> Result<TokenResponse<TExtras>>
+    where
+        TExtras: DeserializeOwned;
+
+    /// Parse the token with your parser, which receives the `Content-Type` and the body,
+    /// e.g. into `TokenResponse<TExtras>` with your `TExtras`.
+    async fn parse_oauth_token_with<TExtras, F>(self, parser: F) -> Result<TokenResponse<TExtras>>
+    where
+        F: FnOnce(Option<&str>, &str) -> TokenReaderResult<TokenResponse<TExtras>> + Send;
 }
 
 /*
@@ -58,11 +249,11 @@ where
 }
 */
 
-#[async_trait(?Send)]
+#[async_trait]
 impl<T, E> TokenReaderFuture for T
 where
-    T: Future<Output = std::result::Result<Response, E>>,
-    E: Into<Error> + 'static,
+    T: Future<Output = std::result::Result<Response, E>> + Send,
+    E: Into<Error> + Send + 'static,
 {
     async fn parse_oauth_token(self) -> Result<TokenResponse> {
         match self.await {
@@ -70,34 +261,74 @@ where
             Err(err) => Err(err.into()),
         }
     }
+
+    async fn parse_oauth_token_as<TExtras>(self) -> Result<TokenResponse<TExtras>>
+    where
+        TExtras: DeserializeOwned,
+    {
+        match self.await {
+            Ok(resp) => Ok(resp.parse_oauth_token_as().await?),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn parse_oauth_token_with<TExtras, F>(self, parser: F) -> Result<TokenResponse<TExtras>>
+    where
+        F: FnOnce(Option<&str>, &str) -> TokenReaderResult<TokenResponse<TExtras>> + Send,
+    {
+        match self.await {
+            Ok(resp) => Ok(resp.parse_oauth_token_with(parser).await?),
+            Err(err) => Err(err.into()),
+        }
+    }
 }
 
-fn read_oauth_token(text: String) -> TokenReaderResult<TokenResponse> { 
-    let mut destructured = text
-        .split("&")
-        .map(|e| e.splitn(2, "="))
-        .map(|v| {
-            let mut iter = v.into_iter();
-            (
-                iter.next().unwrap_or_default().to_string(),
-                iter.next().unwrap_or_default().to_string(),
-            )
-        })
-        .collect::<HashMap<String, String>>();
-    let oauth_token = destructured.remove(OAUTH_TOKEN_KEY);
-    let oauth_token_secret = destructured.remove(OAUTH_TOKEN_SECRET_KEY);
-    match (oauth_token, oauth_token_secret) {
-        (Some(t), Some(s)) => Ok(TokenResponse {
-            oauth_token: t,
-            oauth_token_secret: s,
-            remain: destructured,
-        }),
-        (None, _) => Err(TokenReaderError::TokenKeyNotFound(OAUTH_TOKEN_KEY, text)),
-        (_, _) => Err(TokenReaderError::TokenKeyNotFound(
-            OAUTH_TOKEN_SECRET_KEY,
-            text,
-        )),
+/// Check the status of the response, and parse the token from the body.
+pub(crate) fn read_token_response<TExtras, F>(
+    status: StatusCode,
+    headers: HeaderMap,
+    text: String,
+    parser: F,
+) -> Result<TokenResponse<TExtras>>
+where
+    F: FnOnce(Option<&str>, &str) -> TokenReaderResult<TokenResponse<TExtras>>,
+{
+    if !status.is_success() {
+        let response = ErrorResponse::new(status, headers, text);
+        return Err(match ProblemReport::extract(response) {
+            Ok(report) => Error::Problem(Box::new(report)),
+            Err(response) => TokenReaderError::ErrorResponse(Box::new(response)).into(),
+        });
     }
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok());
+    Ok(parser(content_type, &text)?)
+}
+
+/// Some providers return the form with `text/plain` or without `Content-Type`.
+fn is_urlencoded(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers.get(CONTENT_TYPE) else {
+        return true;
+    };
+    let Ok(content_type) = content_type.to_str() else {
+        return false;
+    };
+    let mime = mime_of(content_type);
+    mime.eq_ignore_ascii_case("application/x-www-form-urlencoded")
+        || mime.eq_ignore_ascii_case("text/plain")
+}
+
+/// The media type without the parameters, e.g. `text/plain` of `text/plain; charset=utf-8`.
+fn mime_of(content_type: &str) -> &str {
+    content_type.split(';').next().unwrap_or_default().trim()
+}
+
+/// `application/json`, or the structured syntax suffix `+json`.
+#[cfg(feature = "json")]
+fn is_json(mime: &str) -> bool {
+    let mime = mime.to_ascii_lowercase();
+    mime == "application/json" || mime.ends_with("+json")
 }
 
 mod private {
@@ -123,12 +354,43 @@ mod private {
 mod test {
 
     use super::*;
+    use crate::{
+        LocalSigningBackend, OAuthBackendProvider, OAuthClientProvider, OAuthParameters,
+        OAuthProblem, Secrets,
+    };
+
+    fn assert_send<T: Send>(_: &T) {}
+
+    #[test]
+    fn token_reader_future_is_send() {
+        let client = reqwest::Client::new();
+        let endpoint = "https://example.com/oauth/request_token";
+
+        let future = client
+            .clone()
+            .oauth1(Secrets::new("consumer", "secret"))
+            .post(endpoint)
+            .send()
+            .parse_oauth_token();
+        assert_send(&future);
+
+        let future = client
+            .oauth1_with_backend(
+                Secrets::new("consumer", "secret"),
+                OAuthParameters::new(),
+                LocalSigningBackend::hmac_sha1("secret", None),
+            )
+            .post(endpoint)
+            .send()
+            .parse_oauth_token();
+        assert_send(&future);
+    }
 
     #[test]
     fn parse_response_typical() {
         let resp_str_sample = "oauth_token=Z6eEdO8MOmk394WozF5oKyuAv855l4Mlqo7hhlSLik&oauth_token_secret=Kd75W4OQfb2oJTV0vzGzeXftVAwgMnEK9MumzYcM&oauth_callback_confirmed=true";
         for parsed in &[
-            read_oauth_token(resp_str_sample.to_string()).unwrap(),
+            <TokenResponse>::from_urlencoded(resp_str_sample).unwrap(),
             serde_urlencoded::from_str::<TokenResponse>(resp_str_sample).unwrap(),
         ] {
             assert_eq!(
@@ -149,7 +411,7 @@ mod test {
     fn parse_response_edge() {
         let resp_str_sample = "oauth_token==&oauth_token_secret=&keyonly=&keyonly2&=&&";
         for parsed in &[
-            read_oauth_token(resp_str_sample.to_string()).unwrap(),
+            <TokenResponse>::from_urlencoded(resp_str_sample).unwrap(),
             serde_urlencoded::from_str::<TokenResponse>(resp_str_sample).unwrap(),
         ] {
             assert_eq!(parsed.oauth_token, "=");
@@ -167,35 +429,214 @@ mod test {
     #[test]
     fn parse_minimal() {
         let resp_str_sample = "oauth_token&oauth_token_secret";
-        let parsed = read_oauth_token(resp_str_sample.to_string()).unwrap();
+        let parsed = <TokenResponse>::from_urlencoded(resp_str_sample).unwrap();
         assert_eq!(parsed.oauth_token, "");
         assert_eq!(parsed.oauth_token_secret, "");
         assert_eq!(parsed.remain.len(), 0);
     }
 
+    #[test]
+    fn reject_error_status() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, "text/html; charset=utf-8".parse().unwrap());
+        let body = "<html><body>Unauthorized</body></html>";
+        let parsed = read_token_response(
+            StatusCode::UNAUTHORIZED,
+            headers,
+            body.to_string(),
+            <TokenResponse>::from_body,
+        );
+        if let Err(Error::TokenReader(TokenReaderError::ErrorResponse(resp))) = parsed {
+            assert_eq!(resp.status, StatusCode::UNAUTHORIZED);
+            assert_eq!(
+                resp.headers.get(CONTENT_TYPE).unwrap(),
+                "text/html; charset=utf-8"
+            );
+            assert_eq!(resp.body, body);
+            assert!(resp.parameters.is_empty());
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn parse_error_parameters() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            CONTENT_TYPE,
+            "application/x-www-form-urlencoded".parse().unwrap(),
+        );
+        let body = "error=invalid_request&error_description=the%20token%20is%20expired";
+        let parsed = read_token_response(
+            StatusCode::BAD_REQUEST,
+            headers,
+            body.to_string(),
+            <TokenResponse>::from_body,
+        );
+        if let Err(Error::TokenReader(TokenReaderError::ErrorResponse(resp))) = parsed {
+            assert_eq!(resp.status, StatusCode::BAD_REQUEST);
+            assert_eq!(resp.parameters["error"], "invalid_request");
+            assert_eq!(resp.parameters["error_description"], "the token is expired");
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn reject_reported_problem() {
+        let body = "oauth_problem=token_rejected&oauth_problem_advice=the%20token%20is%20expired";
+        let parsed = read_token_response(
+            StatusCode::UNAUTHORIZED,
+            HeaderMap::new(),
+            body.into(),
+            <TokenResponse>::from_body,
+        );
+        if let Err(Error::Problem(report)) = parsed {
+            assert_eq!(report.problem, OAuthProblem::TokenRejected);
+            assert_eq!(report.advice.as_deref(), Some("the token is expired"));
+            assert_eq!(report.response.body, body);
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn parse_percent_encoded() {
+        let parsed = <TokenResponse>::from_urlencoded(
+            "oauth_token=a%2Fb&oauth_token_secret=c%3Dd&screen_name=John+Doe",
+        )
+        .unwrap();
+        assert_eq!(parsed.oauth_token, "a/b");
+        assert_eq!(parsed.oauth_token_secret, "c=d");
+        assert_eq!(parsed.remain["screen_name"], "John Doe");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn parse_json_by_content_type() {
+        let body = r#"{"oauth_token":"a/b","oauth_token_secret":"c=d","user_id":6253282,"oauth_callback_confirmed":true,"state":null}"#;
+        let parsed =
+            <TokenResponse>::from_body(Some("application/json; charset=utf-8"), body).unwrap();
+        assert_eq!(parsed.oauth_token, "a/b");
+        assert_eq!(parsed.oauth_token_secret, "c=d");
+        assert_eq!(parsed.remain.len(), 2);
+        assert_eq!(parsed.remain["user_id"], "6253282");
+        assert_eq!(parsed.remain["oauth_callback_confirmed"], "true");
+
+        let parsed = <TokenResponse>::from_body(Some("application/json"), "oauth_token=a");
+        assert!(matches!(
+            parsed,
+            Err(TokenReaderError::MalformedResponse(_))
+        ));
+    }
+
+    #[test]
+    fn parse_with_custom_parser() {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, "text/csv".parse().unwrap());
+        let parsed: TokenResponse = read_token_response(
+            StatusCode::OK,
+            headers,
+            "a/b,c=d".into(),
+            |content_type, body| {
+                assert_eq!(content_type, Some("text/csv"));
+                let (token, secret) = body
+                    .split_once(',')
+                    .ok_or_else(|| TokenReaderError::MalformedResponse(body.to_string()))?;
+                Ok(TokenResponse {
+                    oauth_token: token.to_string(),
+                    oauth_token_secret: secret.to_string(),
+                    remain: HashMap::new(),
+                })
+            },
+        )
+        .unwrap();
+        assert_eq!(parsed.oauth_token, "a/b");
+        assert_eq!(parsed.oauth_token_secret, "c=d");
+    }
+
+    #[test]
+    fn parse_typed_extras() {
+        #[derive(Deserialize)]
+        struct Extras {
+            user_id: u64,
+            screen_name: String,
+            oauth_expires_in: Option<u64>,
+        }
+
+        let resp_str_sample = "oauth_token=6253282-eWudHldSbIaelX7swmsiHImEL4KinwaGloHANdrY&oauth_token_secret=2EEfA6BG5ly3sR3XjE0IBSnlQu4ZrUzPiYTmrkVU&user_id=6253282&screen_name=twitterapi";
+        let parsed = TokenResponse::<Extras>::from_urlencoded(resp_str_sample).unwrap();
+        assert_eq!(
+            parsed.oauth_token,
+            "6253282-eWudHldSbIaelX7swmsiHImEL4KinwaGloHANdrY"
+        );
+        assert_eq!(parsed.remain.user_id, 6253282);
+        assert_eq!(parsed.remain.screen_name, "twitterapi");
+        assert_eq!(parsed.remain.oauth_expires_in, None);
+
+        let parsed = TokenResponse::<Extras>::from_urlencoded(
+            "oauth_token=a&oauth_token_secret=b&user_id=me&screen_name=twitterapi",
+        );
+        assert!(matches!(
+            parsed,
+            Err(TokenReaderError::MalformedResponse(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn parse_typed_extras_with_custom_parser() {
+        #[derive(Deserialize)]
+        struct Extras {
+            user_id: u64,
+        }
+
+        let response = http::Response::builder()
+            .header(CONTENT_TYPE, "text/plain")
+            .body("oauth_token=a&oauth_token_secret=b&user_id=6253282")
+            .unwrap();
+        let parsed = Response::from(response)
+            .parse_oauth_token_with(|_, body| TokenResponse::<Extras>::from_urlencoded(body))
+            .await
+            .unwrap();
+        assert_eq!(parsed.oauth_token, "a");
+        assert_eq!(parsed.remain.user_id, 6253282);
+    }
+
+    #[test]
+    fn refuse_unconfirmed_callback() {
+        let parsed = <TokenResponse>::from_urlencoded(
+            "oauth_token=a&oauth_token_secret=b&oauth_callback_confirmed=false",
+        );
+        if let Err(TokenReaderError::CallbackNotConfirmed(value)) = parsed {
+            assert_eq!(value, "false");
+        } else {
+            unreachable!()
+        }
+    }
+
     #[test]
     fn parse_token_notfound() {
         let resp_str_sample = "oauth_token_secret=";
-        let parsed = read_oauth_token(resp_str_sample.to_string());
+        let parsed = <TokenResponse>::from_urlencoded(resp_str_sample);
         assert!(parsed.is_err());
         if let Err(TokenReaderError::TokenKeyNotFound(key, resp_str)) = parsed {
             assert_eq!(key, OAUTH_TOKEN_KEY);
             assert_eq!(resp_str, resp_str_sample)
         } else {
-            assert!(false)
+            unreachable!()
         }
     }
 
     #[test]
     fn parse_token_secret_notfound() {
         let resp_str_sample = "oauth_token=";
-        let parsed = read_oauth_token(resp_str_sample.to_string());
+        let parsed = <TokenResponse>::from_urlencoded(resp_str_sample);
         assert!(parsed.is_err());
         if let Err(TokenReaderError::TokenKeyNotFound(key, resp_str)) = parsed {
             assert_eq!(key, OAUTH_TOKEN_SECRET_KEY);
             assert_eq!(resp_str, resp_str_sample)
         } else {
-            assert!(false)
+            unreachable!()
         }
     }
 }
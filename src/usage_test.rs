@@ -7,16 +7,8 @@ use tokio;
 const CONSUMER_KEY: &str = "[CONSUMER_KEY]";
 const CONSUMER_SECRET: &str = "[CONSUMER_SECRET]";
 
-#[cfg(feature = "blocking")]
-use reqwest::blocking::Client as ReqwestClient;
-
-#[cfg(not(feature = "blocking"))]
 use crate::{OAuthClientProvider, Secrets, TokenReaderFuture};
 
-#[cfg(feature = "blocking")]
-use crate::{OAuthClientProvider, Secrets, TokenReaderBlocking};
-
-#[cfg(not(feature = "blocking"))]
 use reqwest::Client as ReqwestClient;
 
 #[test]
@@ -26,7 +18,6 @@ fn map_test() {
     println!("{:#?}", map)
 }
 
-#[cfg(not(feature = "blocking"))]
 #[tokio::test]
 async fn usage_test() {
     // prepare authorization info
@@ -83,7 +74,9 @@ async fn usage_test() {
 
 #[cfg(feature = "blocking")]
 #[test]
-fn usage_test() {
+fn usage_test_blocking() {
+    use crate::blocking::TokenReaderBlocking;
+
     // prepare authorization info
 
     let secrets = Secrets::new(CONSUMER_KEY, CONSUMER_SECRET);
@@ -93,7 +86,7 @@ fn usage_test() {
     // step 1: acquire request token & token secret
     let endpoint_reqtoken = "https://api.twitter.com/oauth/request_token";
 
-    let client = ReqwestClient::new();
+    let client = reqwest::blocking::Client::new();
     let resp = client
         .oauth1(secrets)
         .post(endpoint_reqtoken)
@@ -119,7 +112,7 @@ fn usage_test() {
         .token(resp.oauth_token, resp.oauth_token_secret);
     let endpoint_acctoken = "https://api.twitter.com/oauth/access_token";
 
-    let client = ReqwestClient::new();
+    let client = reqwest::blocking::Client::new();
     let resp = client
         .oauth1(secrets)
         .post(endpoint_acctoken)